@@ -0,0 +1,141 @@
+//! Check that arrays of double-precision uniforms are correctly uploaded.
+//!
+//! An array of `dvec3` and an array of `dmat4` are uploaded, and each element contributes to a
+//! different channel of the rendered color. The result is read back from an offscreen framebuffer;
+//! a driver uploading only the first element of the arrays, or messing with their strides, renders
+//! a wrong color.
+
+use crate::{Example, InputAction, LoopFeedback, PlatformServices};
+use luminance::{shader::types::Arr, UniformInterface};
+use luminance_front::{
+  context::GraphicsContext,
+  framebuffer::Framebuffer,
+  pipeline::PipelineState,
+  pixel::NormRGBA8UI,
+  render_state::RenderState,
+  shader::{
+    types::{Mat44, Vec3},
+    Uniform,
+  },
+  tess::Mode,
+  texture::{Dim2, Sampler},
+  Backend,
+};
+
+const VS: &str = "
+const vec2[4] POSITIONS = vec2[](
+  vec2(-1., -1.),
+  vec2( 1., -1.),
+  vec2( 1.,  1.),
+  vec2(-1.,  1.)
+);
+
+void main() {
+  gl_Position = vec4(POSITIONS[gl_VertexID], 0., 1.);
+}";
+
+const FS: &str = "
+out vec4 frag;
+
+uniform dvec3 colors[2];
+uniform dmat4 masks[2];
+
+void main() {
+  dvec4 color = masks[0] * dvec4(colors[0], 1.) + masks[1] * dvec4(colors[1], 1.);
+  frag = vec4(vec3(color.xyz), 1.);
+}";
+
+#[derive(Debug, UniformInterface)]
+struct ShaderInterface {
+  colors: Uniform<Arr<Vec3<f64>, 2>>,
+  masks: Uniform<Arr<Mat44<f64>, 2>>,
+}
+
+/// Diagonal matrix keeping only the channels whose mask component is `1`.
+fn mask(x: f64, y: f64, z: f64) -> Mat44<f64> {
+  Mat44::new([
+    [x, 0., 0., 0.],
+    [0., y, 0., 0.],
+    [0., 0., z, 0.],
+    [0., 0., 0., 0.],
+  ])
+}
+
+pub struct LocalExample;
+
+impl Example for LocalExample {
+  fn bootstrap(
+    _: &mut impl PlatformServices,
+    context: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> Self {
+    let mut program = context
+      .new_shader_program::<(), (), ShaderInterface>()
+      .from_strings(VS, None, None, FS)
+      .unwrap()
+      .ignore_warnings();
+
+    let tess = context
+      .new_tess()
+      .set_mode(Mode::TriangleFan)
+      .set_render_vertex_nb(4)
+      .build()
+      .unwrap();
+
+    let mut framebuffer = context
+      .new_framebuffer::<Dim2, NormRGBA8UI, ()>([16, 16], 0, Sampler::default())
+      .unwrap();
+
+    // the red channel comes from the first elements, green and blue from the second ones; the
+    // other channels are set to values that must be masked out
+    let colors = [Vec3::new(0.25, 1., 1.), Vec3::new(1., 0.5, 0.75)];
+    let masks = [mask(1., 0., 0.), mask(0., 1., 1.)];
+
+    context
+      .new_pipeline_gate()
+      .pipeline(
+        &framebuffer,
+        &PipelineState::default(),
+        |_, mut shd_gate| {
+          shd_gate.shade(&mut program, |mut iface, uni, mut rdr_gate| {
+            iface.set(&uni.colors, &colors);
+            iface.set(&uni.masks, &masks);
+
+            rdr_gate.render(&RenderState::default(), |mut tess_gate| {
+              tess_gate.render(&tess)
+            })
+          })
+        },
+      )
+      .assume()
+      .into_result()
+      .unwrap();
+
+    let texels = framebuffer.color_slot().get_raw_texels().unwrap();
+
+    let expected = [63..=64, 127..=128, 191..=192];
+    for texel in texels.chunks(4) {
+      assert!(
+        expected
+          .iter()
+          .zip(texel)
+          .all(|(range, value)| range.contains(value)),
+        "wrong color rendered from f64 uniform arrays: {:?}",
+        texel
+      );
+    }
+
+    log::info!("arrays of f64 uniforms are correctly uploaded");
+
+    LocalExample
+  }
+
+  fn render_frame(
+    self,
+    _: f32,
+    _: Framebuffer<Dim2, (), ()>,
+    _: impl Iterator<Item = InputAction>,
+    _: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> LoopFeedback<Self> {
+    LoopFeedback::Exit
+  }
+}
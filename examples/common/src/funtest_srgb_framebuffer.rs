@@ -0,0 +1,71 @@
+//! Check that rendering to an sRGB framebuffer gamma-converts the written colors if and only if
+//! the sRGB conversion of the pipeline is enabled.
+//!
+//! With `PipelineState::enable_srgb`, `GL_FRAMEBUFFER_SRGB` is enabled and linear colors written
+//! to sRGB attachments are encoded to sRGB; without it, they are stored as-is.
+
+use crate::{Example, InputAction, LoopFeedback, PlatformServices};
+use luminance_front::{
+  context::GraphicsContext,
+  framebuffer::Framebuffer,
+  pipeline::{PipelineError, PipelineState},
+  pixel::SRGBA8UI,
+  texture::{Dim2, Sampler},
+  Backend,
+};
+
+pub struct LocalExample;
+
+impl Example for LocalExample {
+  fn bootstrap(
+    _: &mut impl PlatformServices,
+    context: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> Self {
+    let mut framebuffer = context
+      .new_framebuffer::<Dim2, SRGBA8UI, ()>([16, 16], 0, Sampler::default())
+      .unwrap();
+
+    // a linear 0.5 is encoded to sRGB as 0.735, stored as 187 or 188; unconverted, it is stored as
+    // 127 or 128
+    for (srgb, expected) in [(false, 127..=128), (true, 187..=188)] {
+      context
+        .new_pipeline_gate()
+        .pipeline(
+          &framebuffer,
+          &PipelineState::default()
+            .set_clear_color([0.5, 0.5, 0.5, 1.])
+            .enable_srgb(srgb),
+          |_, _| Ok::<_, PipelineError>(()),
+        )
+        .assume()
+        .into_result()
+        .unwrap();
+
+      let texels = framebuffer.color_slot().get_raw_texels().unwrap();
+
+      for texel in texels.chunks(4) {
+        assert!(
+          texel[..3].iter().all(|c| expected.contains(c)),
+          "wrong sRGB texel (sRGB pipeline: {}): {:?}",
+          srgb,
+          texel
+        );
+        assert_eq!(texel[3], 255, "alpha must not be converted");
+      }
+    }
+
+    log::info!("sRGB framebuffers are gamma-converted only by sRGB pipelines");
+
+    LocalExample
+  }
+
+  fn render_frame(
+    self,
+    _: f32,
+    _: Framebuffer<Dim2, (), ()>,
+    _: impl Iterator<Item = InputAction>,
+    _: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> LoopFeedback<Self> {
+    LoopFeedback::Exit
+  }
+}
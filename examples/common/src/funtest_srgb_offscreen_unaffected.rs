@@ -0,0 +1,70 @@
+//! Check that rendering to a linear offscreen framebuffer is not gamma-converted, whether the
+//! sRGB conversion of the pipeline is enabled or not.
+//!
+//! Asking the platform for an sRGB default framebuffer must not affect offscreen passes:
+//! `GL_FRAMEBUFFER_SRGB` only converts colors written to sRGB attachments, and luminance only
+//! enables it for pipelines asking for it with `PipelineState::enable_srgb`.
+
+use crate::{Example, InputAction, LoopFeedback, PlatformServices};
+use luminance_front::{
+  context::GraphicsContext,
+  framebuffer::Framebuffer,
+  pipeline::{PipelineError, PipelineState},
+  pixel::NormRGBA8UI,
+  texture::{Dim2, Sampler},
+  Backend,
+};
+
+pub struct LocalExample;
+
+impl Example for LocalExample {
+  fn bootstrap(
+    _: &mut impl PlatformServices,
+    context: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> Self {
+    let mut framebuffer = context
+      .new_framebuffer::<Dim2, NormRGBA8UI, ()>([16, 16], 0, Sampler::default())
+      .unwrap();
+
+    for srgb in [false, true] {
+      context
+        .new_pipeline_gate()
+        .pipeline(
+          &framebuffer,
+          &PipelineState::default()
+            .set_clear_color([0.5, 0.5, 0.5, 1.])
+            .enable_srgb(srgb),
+          |_, _| Ok::<_, PipelineError>(()),
+        )
+        .assume()
+        .into_result()
+        .unwrap();
+
+      let texels = framebuffer.color_slot().get_raw_texels().unwrap();
+
+      // a linear 0.5 is stored as 127 or 128; gamma-converted, it would be stored as 188
+      for texel in texels.chunks(4) {
+        assert!(
+          (127..=128).contains(&texel[0]),
+          "offscreen texel gamma-converted (sRGB pipeline: {}): {:?}",
+          srgb,
+          texel
+        );
+      }
+    }
+
+    log::info!("offscreen RGBA8 framebuffers are not gamma-converted");
+
+    LocalExample
+  }
+
+  fn render_frame(
+    self,
+    _: f32,
+    _: Framebuffer<Dim2, (), ()>,
+    _: impl Iterator<Item = InputAction>,
+    _: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> LoopFeedback<Self> {
+    LoopFeedback::Exit
+  }
+}
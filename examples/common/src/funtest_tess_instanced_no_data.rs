@@ -0,0 +1,180 @@
+use crate::{Example, InputAction, LoopFeedback, PlatformServices};
+use luminance::UniformInterface;
+use luminance_front::{
+  context::GraphicsContext,
+  framebuffer::Framebuffer,
+  pipeline::PipelineState,
+  pixel::NormRGBA8UI,
+  render_state::RenderState,
+  shader::{Program, Uniform},
+  tess::{Mode, Tess},
+  texture::{Dim2, Sampler},
+  Backend,
+};
+
+/// Number of instances; each one covers a vertical strip of the framebuffer.
+const INSTANCE_NB: u32 = 4;
+
+/// Size of the offscreen framebuffer used to check the instances.
+const CHECK_SIZE: u32 = 64;
+
+const VS: &str = "
+const vec2[4] POSITIONS = vec2[](
+  vec2(0., -1.),
+  vec2(1., -1.),
+  vec2(1.,  1.),
+  vec2(0.,  1.)
+);
+
+uniform int instance_nb;
+
+flat out int v_instance;
+
+void main() {
+  vec2 p = POSITIONS[gl_VertexID];
+  float width = 2. / float(instance_nb);
+
+  gl_Position = vec4(-1. + (float(gl_InstanceID) + p.x) * width, p.y, 0., 1.);
+  v_instance = gl_InstanceID;
+}";
+
+const FS: &str = "
+uniform int instance_nb;
+
+flat in int v_instance;
+
+out vec4 frag;
+
+void main() {
+  frag = vec4(1., float(v_instance + 1) / float(instance_nb), 0., 1.);
+}";
+
+#[derive(Debug, UniformInterface)]
+struct ShaderInterface {
+  instance_nb: Uniform<i32>,
+}
+
+/// Render the instances into an offscreen framebuffer and check that each strip was covered by
+/// its own instance.
+fn check_instances(
+  context: &mut impl GraphicsContext<Backend = Backend>,
+  program: &mut Program<(), (), ShaderInterface>,
+  tess: &Tess<()>,
+) {
+  let mut framebuffer = context
+    .new_framebuffer::<Dim2, NormRGBA8UI, ()>([CHECK_SIZE, CHECK_SIZE], 0, Sampler::default())
+    .unwrap();
+
+  context
+    .new_pipeline_gate()
+    .pipeline(
+      &framebuffer,
+      &PipelineState::default().set_clear_color([0., 0., 0., 1.]),
+      |_, mut shd_gate| {
+        shd_gate.shade(program, |mut iface, uni, mut rdr_gate| {
+          iface.set(&uni.instance_nb, INSTANCE_NB as i32);
+          rdr_gate.render(&RenderState::default(), |mut tess_gate| {
+            tess_gate.render(tess)
+          })
+        })
+      },
+    )
+    .assume()
+    .into_result()
+    .unwrap();
+
+  let texels = framebuffer.color_slot().get_raw_texels().unwrap();
+  let strip_width = CHECK_SIZE / INSTANCE_NB;
+
+  for y in 0..CHECK_SIZE {
+    for x in 0..CHECK_SIZE {
+      let offset = ((y * CHECK_SIZE + x) * 4) as usize;
+      let (red, green) = (texels[offset], texels[offset + 1]);
+      let instance = x / strip_width;
+      let expected_green = ((instance + 1) as f32 / INSTANCE_NB as f32 * 255.).round() as i32;
+
+      assert_eq!(red, 255, "texel ({}, {}) was not rendered", x, y);
+      // normalized conversions might round either way
+      assert!(
+        (green as i32 - expected_green).abs() <= 1,
+        "texel ({}, {}) should be rendered by instance {}, got green {} instead of {}",
+        x,
+        y,
+        instance,
+        green,
+        expected_green,
+      );
+    }
+  }
+
+  log::info!("all {} attribute-less instances rendered", INSTANCE_NB);
+}
+
+pub struct LocalExample {
+  program: Program<(), (), ShaderInterface>,
+  tess: Tess<()>,
+}
+
+impl Example for LocalExample {
+  fn bootstrap(
+    _: &mut impl PlatformServices,
+    context: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> Self {
+    let mut program = context
+      .new_shader_program::<(), (), ShaderInterface>()
+      .from_strings(VS, None, None, FS)
+      .unwrap()
+      .ignore_warnings();
+
+    let tess = context
+      .new_tess()
+      .set_mode(Mode::TriangleFan)
+      .set_render_vertex_nb(4)
+      .set_render_instance_nb(INSTANCE_NB as usize)
+      .build()
+      .unwrap();
+
+    check_instances(context, &mut program, &tess);
+
+    LocalExample { program, tess }
+  }
+
+  fn render_frame(
+    mut self,
+    _: f32,
+    back_buffer: Framebuffer<Dim2, (), ()>,
+    actions: impl Iterator<Item = InputAction>,
+    context: &mut impl GraphicsContext<Backend = Backend>,
+  ) -> LoopFeedback<Self> {
+    for action in actions {
+      if let InputAction::Quit = action {
+        return LoopFeedback::Exit;
+      }
+    }
+
+    let program = &mut self.program;
+    let tess = &self.tess;
+
+    let render = context
+      .new_pipeline_gate()
+      .pipeline(
+        &back_buffer,
+        &PipelineState::default(),
+        |_, mut shd_gate| {
+          shd_gate.shade(program, |mut iface, uni, mut rdr_gate| {
+            iface.set(&uni.instance_nb, INSTANCE_NB as i32);
+            rdr_gate.render(&RenderState::default(), |mut tess_gate| {
+              tess_gate.render(tess)
+            })
+          })
+        },
+      )
+      .assume();
+
+    if render.is_ok() {
+      LoopFeedback::Continue(self)
+    } else {
+      LoopFeedback::Exit
+    }
+  }
+}
@@ -60,11 +60,19 @@ pub mod funtest_483_indices_mut_corruption;
 pub mod funtest_flatten_slice;
 #[cfg(all(feature = "funtest", feature = "funtest-gl33-f64-uniform"))]
 pub mod funtest_gl33_f64_uniform;
+#[cfg(all(feature = "funtest", feature = "funtest-gl33-f64-uniform"))]
+pub mod funtest_gl33_f64_uniform_array;
 #[cfg(feature = "funtest")]
 pub mod funtest_pixel_array_encoding;
 #[cfg(feature = "funtest")]
 pub mod funtest_scissor_test;
 #[cfg(feature = "funtest")]
+pub mod funtest_srgb_framebuffer;
+#[cfg(feature = "funtest")]
+pub mod funtest_srgb_offscreen_unaffected;
+#[cfg(feature = "funtest")]
+pub mod funtest_tess_instanced_no_data;
+#[cfg(feature = "funtest")]
 pub mod funtest_tess_no_data;
 
 /// Example interface.
@@ -237,12 +237,16 @@ examples! {
   // functional tests
   funtests:
   "funtest-tess-no-data", funtest_tess_no_data,
+  "funtest-tess-instanced-no-data", funtest_tess_instanced_no_data,
   "funtest-gl33-f64-uniform" if "funtest-gl33-f64-uniform", funtest_gl33_f64_uniform,
+  "funtest-gl33-f64-uniform-array" if "funtest-gl33-f64-uniform", funtest_gl33_f64_uniform_array,
   "funtest-scissor-test", funtest_scissor_test,
   "funtest-360-manually-drop-framebuffer", funtest_360_manually_drop_framebuffer,
   "funtest-flatten-slice", funtest_flatten_slice,
   "funtest-pixel-array-encoding", funtest_pixel_array_encoding,
   "funtest-483-indices-mut-corruption", funtest_483_indices_mut_corruption,
+  "funtest-srgb-offscreen-unaffected", funtest_srgb_offscreen_unaffected,
+  "funtest-srgb-framebuffer", funtest_srgb_framebuffer,
 }
 
 fn main() {
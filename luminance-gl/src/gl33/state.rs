@@ -296,6 +296,16 @@ impl GLState {
     }
   }
 
+  /// Reset the state from the current context.
+  ///
+  /// This is needed when the underlying OpenGL context is replaced by another one (for instance
+  /// after a context loss): the whole state is queried again from the current context, as if the
+  /// [`GLState`] was just created. All the objects of the previous context must have been dropped.
+  pub fn reset(&mut self) -> Result<(), StateQueryError> {
+    *self = Self::get_from_context()?;
+    Ok(())
+  }
+
   /// Invalidate the currently in-use vertex array.
   pub fn invalidate_vertex_array(&mut self) {
     self.bound_vertex_array = 0;
@@ -350,6 +360,16 @@ impl GLState {
     self.clear_color.invalidate()
   }
 
+  /// Invalidate the currently in-use clear depth.
+  pub fn invalidate_clear_depth(&mut self) {
+    self.clear_depth.invalidate()
+  }
+
+  /// Invalidate the currently in-use clear stencil.
+  pub fn invalidate_clear_stencil(&mut self) {
+    self.clear_stencil.invalidate()
+  }
+
   /// Invalidate the currently in-use blending state.
   pub fn invalidate_blending_state(&mut self) {
     self.blending_state.invalidate()
@@ -380,6 +400,21 @@ impl GLState {
     self.depth_write.invalidate()
   }
 
+  /// Invalidate the currently in-use stencil test enabled state.
+  pub fn invalidate_stencil_test_enabled(&mut self) {
+    self.stencil_test_enabled.invalidate()
+  }
+
+  /// Invalidate the currently in-use stencil test.
+  pub fn invalidate_stencil_test(&mut self) {
+    self.stencil_test.invalidate()
+  }
+
+  /// Invalidate the currently in-use stencil operations.
+  pub fn invalidate_stencil_operations(&mut self) {
+    self.stencil_operations.invalidate()
+  }
+
   /// Invalidate the currently in-use face culling state.
   pub fn invalidate_face_culling_state(&mut self) {
     self.face_culling_state.invalidate()
@@ -395,6 +430,16 @@ impl GLState {
     self.face_culling_mode.invalidate()
   }
 
+  /// Invalidate the currently in-use scissor state.
+  pub fn invalidate_scissor_state(&mut self) {
+    self.scissor_state.invalidate()
+  }
+
+  /// Invalidate the currently in-use scissor region.
+  pub fn invalidate_scissor_region(&mut self) {
+    self.scissor_region.invalidate()
+  }
+
   /// Invalidate the currently in-use vertex restart state.
   pub fn invalidate_vertex_restart(&mut self) {
     self.vertex_restart.invalidate()
@@ -782,9 +827,14 @@ impl GLState {
 
   /// Bind a texture at the current texture unit.
   pub(crate) unsafe fn bind_texture(&mut self, target: GLenum, handle: GLuint) {
-    // Unwrap should be safe here, because we should always bind the texture unit before we bind the texture.
-    // Maybe this should be handled differently?
-    let unit = self.current_texture_unit.0.unwrap();
+    // the texture unit is unknown after the cache was invalidated; fall back to the first one
+    let unit = match self.current_texture_unit.0 {
+      Some(unit) => unit,
+      None => {
+        self.set_texture_unit(0);
+        0
+      }
+    };
     self.bind_texture_at(target, handle, unit);
   }
 
@@ -0,0 +1,215 @@
+//! Application scaffold driving a surface with lifecycle callbacks.
+
+use crate::{GlutinError, GlutinSurface, SwapResult};
+use glutin::event::{Event, WindowEvent};
+use glutin::event_loop::{ControlFlow, EventLoop};
+use glutin::window::WindowBuilder;
+use std::time::{Duration, Instant};
+
+/// Default duration of an update step: 60 updates per second.
+const DEFAULT_TIMESTEP: Duration = Duration::from_nanos(16_666_667);
+
+/// Longest time accounted for between two frames; longer stalls (e.g. dragging the window on
+/// some platforms) are not caught up with, so that updates cannot fall further and further behind.
+const MAX_FRAME_TIME: Duration = Duration::from_millis(250);
+
+/// Reason why a [`GlutinApp`] stopped.
+///
+/// See [`AppCallbacks::exit`].
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum AppExit {
+  /// The window was closed, or [`AppCallbacks::should_exit`] returned `true`.
+  Closed,
+  /// Presenting a frame reported that the surface or the context was lost
+  /// ([`SwapResult::SurfaceLost`] or [`SwapResult::ContextLost`]).
+  Lost(SwapResult),
+  /// Presenting a frame failed.
+  Error(GlutinError),
+}
+
+/// Lifecycle callbacks of a [`GlutinApp`].
+///
+/// Only [`AppCallbacks::render`] is required; the other callbacks do nothing by default. See
+/// [`GlutinApp::run`] for when they are called.
+pub trait AppCallbacks {
+  /// Called once, before the first event, to create the resources of the application.
+  fn init(&mut self, _surface: &mut GlutinSurface) {}
+
+  /// Called for each event of the window, after the surface handled it (see
+  /// [`GlutinSurface::handle_window_event`]).
+  fn event(&mut self, _surface: &mut GlutinSurface, _event: &WindowEvent) {}
+
+  /// Called when the size of the surface changed, with the new size in physical pixels.
+  ///
+  /// Resizes are coalesced: this is called at most once per frame, before updating.
+  fn resize(&mut self, _surface: &mut GlutinSurface, _size: [u32; 2]) {}
+
+  /// Advance the state of the application by `dt`, which is always the timestep of the app (see
+  /// [`GlutinApp::with_timestep`]).
+  fn update(&mut self, _surface: &mut GlutinSurface, _dt: Duration) {}
+
+  /// Render a frame to the back buffer, without swapping the buffers.
+  ///
+  /// `alpha`, in `[0; 1)`, is how far the current time is between the last update and the next
+  /// one, in timesteps; interpolate between the previous and the current state with it for smooth
+  /// motion.
+  fn render(&mut self, surface: &mut GlutinSurface, alpha: f32);
+
+  /// Whether the application wants to stop; checked after each event and each update.
+  fn should_exit(&self) -> bool {
+    false
+  }
+
+  /// Called once when the application stops, with the reason why.
+  fn exit(&mut self, _surface: &mut GlutinSurface, _reason: AppExit) {}
+}
+
+/// Application owning a [`GlutinSurface`] and its event loop, and driving them with
+/// [`AppCallbacks`].
+///
+/// This is an opinionated scaffold for the common case of a single window rendering continuously
+/// at a fixed update rate. Applications needing more control (rendering on demand, several
+/// windows, recovering from lost contexts, etc.) should drive the surface themselves.
+pub struct GlutinApp {
+  surface: GlutinSurface,
+  event_loop: EventLoop<()>,
+  timestep: Duration,
+}
+
+impl GlutinApp {
+  /// Create an application with a new surface; see [`GlutinSurface::new_gl33`].
+  pub fn new(window_builder: WindowBuilder, samples: u16) -> Result<Self, GlutinError> {
+    let (surface, event_loop) = GlutinSurface::new_gl33(window_builder, samples)?;
+    Ok(Self::from_parts(surface, event_loop))
+  }
+
+  /// Create an application from an existing surface and the event loop it was created on.
+  pub fn from_parts(surface: GlutinSurface, event_loop: EventLoop<()>) -> Self {
+    GlutinApp {
+      surface,
+      event_loop,
+      timestep: DEFAULT_TIMESTEP,
+    }
+  }
+
+  /// Set the duration of an update step. The default is 1/60 second.
+  ///
+  /// # Panics
+  ///
+  /// Panics if `timestep` is zero.
+  pub fn with_timestep(mut self, timestep: Duration) -> Self {
+    assert!(timestep > Duration::ZERO, "the timestep must not be zero");
+    self.timestep = timestep;
+    self
+  }
+
+  /// Get access to the surface, e.g. to configure it before running.
+  pub fn surface(&mut self) -> &mut GlutinSurface {
+    &mut self.surface
+  }
+
+  /// Run the application until it stops, calling `callbacks` along the way.
+  ///
+  /// [`AppCallbacks::init`] is called first. Then, for each iteration of the event loop:
+  ///
+  /// 1. Window events are handled by the surface (see [`GlutinSurface::handle_window_event`]),
+  ///    then passed to [`AppCallbacks::event`]. A close request that isn’t vetoed (see
+  ///    [`GlutinSurface::set_close_handler`]) stops the application.
+  /// 2. Once the pending events are processed, [`AppCallbacks::resize`] is called if the size of
+  ///    the surface changed since the previous frame.
+  /// 3. The time elapsed since the previous frame is added to an accumulator, and
+  ///    [`AppCallbacks::update`] is called once per whole timestep it contains, so that the state
+  ///    advances at the same rate whatever the frame rate. Elapsed times are capped to 250 ms, so
+  ///    long stalls slow the application down instead of triggering a burst of updates.
+  /// 4. [`AppCallbacks::render`] is called with the fraction of timestep left in the accumulator,
+  ///    and the buffers are swapped (see [`GlutinSurface::swap_buffers`]); the frame rate is thus
+  ///    set by vertical synchronization. While the window is minimized, nothing is rendered, and
+  ///    the loop sleeps until the next update is due.
+  ///
+  /// The application stops when the window is closed, when [`AppCallbacks::should_exit`] returns
+  /// `true`, or when presenting fails (lost surface or context included); [`AppCallbacks::exit`]
+  /// is then called with the reason. OpenGL errors reported after presenting a frame
+  /// ([`GlutinError::GlError`]) are ignored. This method doesn’t return, as required by some platforms:
+  /// the process exits afterwards, once `callbacks` and the surface are dropped.
+  pub fn run(self, mut callbacks: impl AppCallbacks + 'static) -> ! {
+    let GlutinApp {
+      mut surface,
+      event_loop,
+      timestep,
+    } = self;
+
+    let mut size = surface.size();
+    let mut last_frame = Instant::now();
+    let mut accumulator = Duration::ZERO;
+    let mut stopped = false;
+
+    callbacks.init(&mut surface);
+
+    event_loop.run(move |event, _, control_flow| {
+      // the event loop might still emit events while stopping
+      if stopped {
+        return;
+      }
+
+      *control_flow = ControlFlow::Poll;
+      let mut exit = None;
+
+      match event {
+        Event::WindowEvent { ref event, .. } => {
+          let outcome = surface.handle_window_event(event);
+          callbacks.event(&mut surface, event);
+
+          if outcome.close_requested {
+            exit = Some(AppExit::Closed);
+          }
+        }
+
+        Event::MainEventsCleared => {
+          let new_size = surface.size();
+
+          if new_size != size && new_size[0] != 0 && new_size[1] != 0 {
+            size = new_size;
+            callbacks.resize(&mut surface, size);
+          }
+
+          let now = Instant::now();
+          accumulator += (now - last_frame).min(MAX_FRAME_TIME);
+          last_frame = now;
+
+          while accumulator >= timestep && !callbacks.should_exit() {
+            callbacks.update(&mut surface, timestep);
+            accumulator -= timestep;
+          }
+
+          if surface.is_minimized() {
+            *control_flow = ControlFlow::WaitUntil(now + timestep.saturating_sub(accumulator));
+          } else {
+            let alpha = accumulator.as_secs_f32() / timestep.as_secs_f32();
+            callbacks.render(&mut surface, alpha);
+
+            match surface.swap_buffers() {
+              Ok(SwapResult::Ok) | Ok(SwapResult::Suboptimal) => (),
+              Ok(lost) => exit = Some(AppExit::Lost(lost)),
+              // the frame was presented anyway
+              Err(GlutinError::GlError(_)) => (),
+              Err(e) => exit = Some(AppExit::Error(e)),
+            }
+          }
+        }
+
+        _ => (),
+      }
+
+      if exit.is_none() && callbacks.should_exit() {
+        exit = Some(AppExit::Closed);
+      }
+
+      if let Some(reason) = exit {
+        callbacks.exit(&mut surface, reason);
+        *control_flow = ControlFlow::Exit;
+        stopped = true;
+      }
+    })
+  }
+}
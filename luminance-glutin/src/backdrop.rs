@@ -0,0 +1,76 @@
+//! Window backdrop effects.
+
+use glutin::window::Window;
+
+/// Backdrop effect drawn by the system behind a window.
+///
+/// See [`GlutinSurface::set_backdrop`].
+///
+/// [`GlutinSurface::set_backdrop`]: crate::GlutinSurface::set_backdrop
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Backdrop {
+  /// No backdrop effect.
+  None,
+  /// Material tinted with the desktop wallpaper, for long-lived windows (Mica, on Windows).
+  Mica,
+  /// Translucent, blurred material, for transient windows (Acrylic, on Windows).
+  Acrylic,
+  /// Mica variant with a stronger tint, for tabbed windows (Mica Alt, on Windows).
+  Tabbed,
+}
+
+/// Set the backdrop effect of `window`, returning whether the platform applied it.
+pub(crate) fn set_backdrop(window: &Window, backdrop: Backdrop) -> bool {
+  platform::set_backdrop(window, backdrop)
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+  use super::Backdrop;
+  use glutin::platform::windows::WindowExtWindows;
+  use glutin::window::Window;
+  use std::mem;
+  use winapi::shared::minwindef::DWORD;
+  use winapi::shared::windef::HWND;
+  use winapi::shared::winerror::SUCCEEDED;
+  use winapi::um::dwmapi::DwmSetWindowAttribute;
+
+  // Windows 11 22H2 (build 22621); missing from winapi
+  const DWMWA_SYSTEMBACKDROP_TYPE: DWORD = 38;
+  const DWMSBT_NONE: u32 = 1;
+  const DWMSBT_MAINWINDOW: u32 = 2;
+  const DWMSBT_TRANSIENTWINDOW: u32 = 3;
+  const DWMSBT_TABBEDWINDOW: u32 = 4;
+
+  pub(super) fn set_backdrop(window: &Window, backdrop: Backdrop) -> bool {
+    let backdrop_type = match backdrop {
+      Backdrop::None => DWMSBT_NONE,
+      Backdrop::Mica => DWMSBT_MAINWINDOW,
+      Backdrop::Acrylic => DWMSBT_TRANSIENTWINDOW,
+      Backdrop::Tabbed => DWMSBT_TABBEDWINDOW,
+    };
+
+    // older versions of Windows reject the attribute
+    let hr = unsafe {
+      DwmSetWindowAttribute(
+        window.hwnd() as HWND,
+        DWMWA_SYSTEMBACKDROP_TYPE,
+        &backdrop_type as *const u32 as *const _,
+        mem::size_of::<u32>() as DWORD,
+      )
+    };
+
+    SUCCEEDED(hr)
+  }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+  use super::Backdrop;
+  use glutin::window::Window;
+
+  pub(super) fn set_backdrop(_: &Window, backdrop: Backdrop) -> bool {
+    backdrop == Backdrop::None
+  }
+}
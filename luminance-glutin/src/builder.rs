@@ -0,0 +1,364 @@
+//! Surface builder.
+
+use crate::{CreationParams, GlutinError, GlutinSurface, Vsync};
+use glutin::{
+  event_loop::EventLoopWindowTarget,
+  window::{Theme, WindowBuilder},
+  Api, ContextBuilder, CreationError, GlProfile, GlRequest, NotCurrent, PixelFormat, Robustness,
+};
+use std::thread;
+use std::time::Duration;
+
+/// Time waited before the first retry of a failed creation; it doubles for each next retry.
+const RETRY_BACKOFF: Duration = Duration::from_millis(100);
+
+/// Exact pixel format of a surface.
+///
+/// Use it with [`GlutinSurfaceBuilder::require_exact_format`] (or
+/// [`GlutinSurfaceBuilder::match_format`]) to get the same pixel format on every machine, or fail.
+///
+/// All the fields are matched exactly: the color, alpha, depth and stencil sizes, and the number
+/// of samples. The other attributes of the pixel format (sRGB capability, double buffering,
+/// stereo, hardware acceleration) are not part of the match, and keep following the other options
+/// of the builder.
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct ExactFormat {
+  /// Number of bits of the color buffer, excluding alpha.
+  pub color_bits: u8,
+  /// Number of bits of the alpha channel of the color buffer.
+  pub alpha_bits: u8,
+  /// Number of bits of the depth buffer.
+  pub depth_bits: u8,
+  /// Number of bits of the stencil buffer.
+  pub stencil_bits: u8,
+  /// Number of samples per pixel; `0` disables multisampling.
+  pub samples: u16,
+}
+
+impl ExactFormat {
+  /// Check whether a pixel format obtained from the platform matches this format.
+  fn matches(&self, pf: &PixelFormat) -> bool {
+    let samples = pf.multisampling.unwrap_or(0);
+
+    pf.color_bits == self.color_bits
+      && pf.alpha_bits == self.alpha_bits
+      && pf.depth_bits == self.depth_bits
+      && pf.stencil_bits == self.stencil_bits
+      && samples == self.samples
+  }
+}
+
+/// Strategy to notify applications of GPU resets, for robust contexts.
+///
+/// See [`GlutinSurfaceBuilder::with_reset_notification`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ResetNotification {
+  /// GPU resets are not notified; the context keeps working, with an undefined content.
+  NoNotification,
+  /// The context is lost on GPU resets, and must be recreated along with all its resources.
+  LoseContextOnReset,
+}
+
+/// Builder of [`GlutinSurface`].
+///
+/// This is the most flexible way to create a [`GlutinSurface`]: you can customize the
+/// [`WindowBuilder`], the [`ContextBuilder`] and options specific to luminance.
+#[derive(Debug)]
+pub struct GlutinSurfaceBuilder<'a> {
+  window_builder: WindowBuilder,
+  ctx_builder: ContextBuilder<'a, NotCurrent>,
+  exact_format: Option<ExactFormat>,
+  retries: u32,
+}
+
+impl<'a> GlutinSurfaceBuilder<'a> {
+  /// Create a new builder for a surface living in the window described by `window_builder`.
+  ///
+  /// The default surface is double buffered, without multisampling.
+  pub fn new(window_builder: WindowBuilder) -> Self {
+    let ctx_builder = ContextBuilder::new()
+      .with_gl(GlRequest::Specific(Api::OpenGl, (3, 3)))
+      .with_gl_profile(GlProfile::Core)
+      .with_double_buffer(Some(true));
+
+    GlutinSurfaceBuilder {
+      window_builder,
+      ctx_builder,
+      exact_format: None,
+      retries: 0,
+    }
+  }
+
+  /// Customize the [`ContextBuilder`] used to create the OpenGL context.
+  ///
+  /// The [`ContextBuilder`] passed to `f` is already initialized for the OpenGL context luminance
+  /// requires; you’re not supposed to change the requested OpenGL version and profile.
+  pub fn with_context_builder(
+    mut self,
+    f: impl FnOnce(ContextBuilder<'a, NotCurrent>) -> ContextBuilder<'a, NotCurrent>,
+  ) -> Self {
+    self.ctx_builder = f(self.ctx_builder);
+    self
+  }
+
+  /// Set the number of samples per pixel of the default framebuffer; `0` disables multisampling.
+  pub fn with_samples(mut self, samples: u16) -> Self {
+    self.ctx_builder = self.ctx_builder.with_multisampling(samples);
+    self
+  }
+
+  /// Set the vertical synchronization of buffer swaps.
+  ///
+  /// Vertical synchronization is disabled by default.
+  pub fn with_vsync(mut self, vsync: Vsync) -> Self {
+    self.ctx_builder = self.ctx_builder.with_vsync(vsync == Vsync::On);
+    self
+  }
+
+  /// Ask for an sRGB-capable default framebuffer.
+  ///
+  /// This only affects the default framebuffer (i.e. the back buffer): textures and offscreen
+  /// framebuffers keep their own pixel formats, so linear textures stay linear. Colors are not
+  /// gamma-converted when written to the back buffer unless the pipeline asks for it with
+  /// [`PipelineState::enable_srgb`], which toggles `GL_FRAMEBUFFER_SRGB` for that pipeline only;
+  /// offscreen passes with a linear color format are never converted.
+  ///
+  /// Whether an sRGB default framebuffer is asked for by default depends on glutin (it currently
+  /// is).
+  ///
+  /// [`PipelineState::enable_srgb`]: luminance::pipeline::PipelineState::enable_srgb
+  pub fn with_srgb(mut self, srgb: bool) -> Self {
+    self.ctx_builder = self.ctx_builder.with_srgb(srgb);
+    self
+  }
+
+  /// Ask for a stereo (quad-buffered) default framebuffer, with left and right back buffers.
+  ///
+  /// Stereo framebuffers are only provided by some drivers (typically professional GPUs) with
+  /// stereo displays. If the platform doesn’t provide one, creating the surface fails. Check
+  /// whether the surface is stereo with [`GlutinSurface::is_stereo`], and render to each eye with
+  /// [`GlutinSurface::back_buffer_stereo`].
+  ///
+  /// No stereo framebuffer is asked for by default.
+  pub fn with_stereo(mut self, stereo: bool) -> Self {
+    self.ctx_builder.pf_reqs.stereoscopy = stereo;
+    self
+  }
+
+  /// Ask for a robust context, with the given GPU reset notification strategy.
+  ///
+  /// Robust contexts check memory accesses to avoid crashes (see
+  /// [`ContextFlags::robust_access`]). With [`ResetNotification::LoseContextOnReset`], a GPU reset
+  /// loses the context: [`GlutinSurface::swap_buffers`] then returns [`SwapResult::ContextLost`],
+  /// and the surface, along with all its resources, must be recreated.
+  ///
+  /// If the platform doesn’t support robust contexts, a non-robust context is created instead.
+  /// By default, no robust context is asked for, and GPU resets are not notified.
+  ///
+  /// [`ContextFlags::robust_access`]: crate::ContextFlags::robust_access
+  /// [`SwapResult::ContextLost`]: crate::SwapResult::ContextLost
+  pub fn with_reset_notification(mut self, notification: ResetNotification) -> Self {
+    let robustness = match notification {
+      ResetNotification::NoNotification => Robustness::TryRobustNoResetNotification,
+      ResetNotification::LoseContextOnReset => Robustness::TryRobustLoseContextOnReset,
+    };
+
+    self.ctx_builder = self.ctx_builder.with_gl_robustness(robustness);
+    self
+  }
+
+  /// Keep the window out of the taskbar, which is useful for tool palettes and splash screens.
+  ///
+  /// # Platform-specific
+  ///
+  /// - **X11:** the window is given the utility window type (`_NET_WM_WINDOW_TYPE_UTILITY`), which
+  ///   most window managers keep out of the taskbar. Requires the `x11` feature.
+  /// - **Windows, macOS, Wayland:** unsupported; this has no effect.
+  ///
+  /// This can only be set when creating the window; it cannot be changed afterwards.
+  pub fn with_skip_taskbar(self, skip_taskbar: bool) -> Self {
+    #[cfg(all(
+      feature = "x11",
+      any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      )
+    ))]
+    {
+      use glutin::platform::unix::{WindowBuilderExtUnix, XWindowType};
+
+      let window_type = if skip_taskbar {
+        XWindowType::Utility
+      } else {
+        XWindowType::Normal
+      };
+
+      GlutinSurfaceBuilder {
+        window_builder: self.window_builder.with_x11_window_type(vec![window_type]),
+        ..self
+      }
+    }
+
+    #[cfg(not(all(
+      feature = "x11",
+      any(
+        target_os = "linux",
+        target_os = "dragonfly",
+        target_os = "freebsd",
+        target_os = "netbsd",
+        target_os = "openbsd"
+      )
+    )))]
+    {
+      let _ = skip_taskbar;
+      self
+    }
+  }
+
+  /// Set the theme of the window (light or dark); `None` follows the theme of the system.
+  ///
+  /// # Platform-specific
+  ///
+  /// - **Windows:** the theme applies to the title bar of the window.
+  /// - **macOS, X11, Wayland:** unsupported; this has no effect.
+  ///
+  /// winit doesn’t allow changing the theme of a window once created. See
+  /// [`GlutinSurface::theme`] to follow the theme of the window.
+  pub fn with_theme(self, theme: Option<Theme>) -> Self {
+    #[cfg(target_os = "windows")]
+    {
+      use glutin::platform::windows::WindowBuilderExtWindows;
+
+      GlutinSurfaceBuilder {
+        window_builder: self.window_builder.with_theme(theme),
+        ..self
+      }
+    }
+
+    #[cfg(not(target_os = "windows"))]
+    {
+      let _ = theme;
+      self
+    }
+  }
+
+  /// Require the default framebuffer to have exactly the given component sizes.
+  ///
+  /// This is [`GlutinSurfaceBuilder::require_exact_format`], with the fields of the
+  /// [`ExactFormat`] passed as arguments.
+  pub fn match_format(
+    self,
+    color_bits: u8,
+    alpha_bits: u8,
+    depth_bits: u8,
+    stencil_bits: u8,
+    samples: u16,
+  ) -> Self {
+    self.require_exact_format(ExactFormat {
+      color_bits,
+      alpha_bits,
+      depth_bits,
+      stencil_bits,
+      samples,
+    })
+  }
+
+  /// Require the default framebuffer to have exactly the given pixel format.
+  ///
+  /// The platform is asked for a pixel format with the attributes of `format` (see
+  /// [`ExactFormat`] for the matched attributes), and the surface creation fails with
+  /// [`GlutinError::ExactFormatUnavailable`] if the obtained pixel format differs in any of them,
+  /// instead of silently using the closest format the driver provides. This gives deterministic
+  /// pixel formats, which is useful for golden-image tests and pipelines relying on specific
+  /// formats.
+  pub fn require_exact_format(mut self, format: ExactFormat) -> Self {
+    self.ctx_builder = self
+      .ctx_builder
+      .with_pixel_format(format.color_bits, format.alpha_bits)
+      .with_depth_buffer(format.depth_bits)
+      .with_stencil_buffer(format.stencil_bits)
+      .with_multisampling(format.samples);
+    self.exact_format = Some(format);
+    self
+  }
+
+  /// Retry creating the window and the OpenGL context up to `retries` times on transient errors.
+  ///
+  /// On slow-starting compositors (e.g. Wayland sessions or containers starting up), creation
+  /// might fail because the display server is not ready yet. With retries, creation is attempted
+  /// again after waiting 100 ms, the wait doubling for each next attempt (100 ms, 200 ms, 400 ms,
+  /// etc.), which blocks the calling thread.
+  ///
+  /// Only the errors reported by the platform are considered transient:
+  /// [`CreationError::OsError`], [`CreationError::Window`], [`CreationError::NoBackendAvailable`],
+  /// and [`CreationError::CreationErrors`] if all the errors it carries are. Other errors (missing
+  /// pixel format, unsupported OpenGL version or robustness, etc.) will not go away by retrying,
+  /// and fail immediately. The default is not to retry.
+  pub fn with_retries(mut self, retries: u32) -> Self {
+    self.retries = retries;
+    self
+  }
+
+  /// Build the [`GlutinSurface`] on the given event loop.
+  pub fn build<T>(
+    self,
+    event_loop: &EventLoopWindowTarget<T>,
+  ) -> Result<GlutinSurface, GlutinError> {
+    let params = CreationParams::new(&self.window_builder, &self.ctx_builder);
+    let requested = self.ctx_builder.pf_reqs.clone();
+    let mut retries = self.retries;
+    let mut backoff = RETRY_BACKOFF;
+
+    let windowed_ctx = loop {
+      let attempt = self
+        .ctx_builder
+        .clone()
+        .build_windowed(self.window_builder.clone(), event_loop);
+
+      match attempt {
+        Ok(windowed_ctx) => break windowed_ctx,
+
+        Err(e) if retries > 0 && is_transient(&e) => {
+          retries -= 1;
+          thread::sleep(backoff);
+          backoff *= 2;
+        }
+
+        Err(e) => return Err(GlutinError::from_creation_error(e, requested)),
+      }
+    };
+
+    let ctx = unsafe { windowed_ctx.make_current().map_err(|(_, e)| e)? };
+
+    // check the pixel format before acquiring the graphics state, as it can be acquired only once
+    if let Some(requested) = self.exact_format {
+      let obtained = ctx.get_pixel_format();
+
+      if !requested.matches(&obtained) {
+        return Err(GlutinError::ExactFormatUnavailable {
+          requested,
+          obtained,
+        });
+      }
+    }
+
+    GlutinSurface::from_current_ctx(ctx, params)
+  }
+}
+
+/// Check whether a creation error might go away by retrying.
+fn is_transient(e: &CreationError) -> bool {
+  match e {
+    CreationError::OsError(_) | CreationError::Window(_) | CreationError::NoBackendAvailable(_) => {
+      true
+    }
+    CreationError::CreationErrors(errors) => {
+      !errors.is_empty() && errors.iter().all(|e| is_transient(e))
+    }
+    _ => false,
+  }
+}
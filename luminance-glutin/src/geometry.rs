@@ -0,0 +1,141 @@
+//! Window geometry persistence.
+
+#[cfg(feature = "serde")]
+use serde_crate::{Deserialize, Serialize};
+
+/// Geometry of a window, to save and restore it across runs.
+///
+/// Get it with [`GlutinSurface::geometry`] and restore it with [`GlutinSurface::apply_geometry`].
+/// With the `serde` feature, it can be serialized, e.g. to a configuration file.
+///
+/// Positions and sizes are in physical pixels.
+///
+/// [`GlutinSurface::geometry`]: crate::GlutinSurface::geometry
+/// [`GlutinSurface::apply_geometry`]: crate::GlutinSurface::apply_geometry
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+#[cfg_attr(
+  feature = "serde",
+  derive(Deserialize, Serialize),
+  serde(crate = "serde_crate")
+)]
+pub struct WindowGeometry {
+  /// Position of the top-left corner of the window, including decorations, on the desktop.
+  ///
+  /// `None` on the platforms that don’t expose window positions (Wayland).
+  pub position: Option<[i32; 2]>,
+  /// Size of the client area of the window.
+  pub size: [u32; 2],
+  /// Whether the window is maximized.
+  pub maximized: bool,
+}
+
+/// Area of a monitor on the desktop: position of its top-left corner, and size.
+pub(crate) type MonitorArea = ([i32; 2], [u32; 2]);
+
+/// Clamp a window to the monitors, so that it is entirely visible.
+///
+/// A window entirely covered by the monitors is left untouched, even if it spans several of them.
+/// Otherwise, it is moved (and shrunk if needed) into the monitor it overlaps the most, or into the
+/// first monitor if it overlaps none (e.g. it was saved on a monitor that is now disconnected).
+/// Monitors are assumed not to overlap each other.
+pub(crate) fn clamp_to_monitors(
+  position: [i32; 2],
+  size: [u32; 2],
+  monitors: &[MonitorArea],
+) -> ([i32; 2], [u32; 2]) {
+  let window = (position, size);
+  let overlaps = monitors.iter().map(|monitor| overlap(window, *monitor));
+  let area = size[0] as u64 * size[1] as u64;
+
+  if overlaps.clone().sum::<u64>() >= area {
+    return window;
+  }
+
+  let best = overlaps
+    .enumerate()
+    .max_by_key(|&(i, overlap)| (overlap, std::cmp::Reverse(i)))
+    .filter(|&(_, overlap)| overlap > 0)
+    .map_or(0, |(i, _)| i);
+
+  match monitors.get(best) {
+    Some(&(monitor_position, monitor_size)) => {
+      let clamp = |i: usize| {
+        let len = size[i].min(monitor_size[i]);
+        let min = monitor_position[i] as i64;
+        let max = min + (monitor_size[i] - len) as i64;
+        ((position[i] as i64).clamp(min, max) as i32, len)
+      };
+      let ((x, width), (y, height)) = (clamp(0), clamp(1));
+
+      ([x, y], [width, height])
+    }
+
+    None => window,
+  }
+}
+
+/// Area of the intersection of two rectangles.
+fn overlap(a: MonitorArea, b: MonitorArea) -> u64 {
+  let axis = |i: usize| {
+    let start = (a.0[i] as i64).max(b.0[i] as i64);
+    let end = (a.0[i] as i64 + a.1[i] as i64).min(b.0[i] as i64 + b.1[i] as i64);
+    (end - start).max(0) as u64
+  };
+
+  axis(0) * axis(1)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const MONITORS: [MonitorArea; 2] = [([0, 0], [1920, 1080]), ([1920, 0], [1280, 1024])];
+
+  #[test]
+  fn inside() {
+    assert_eq!(
+      clamp_to_monitors([100, 100], [800, 600], &MONITORS),
+      ([100, 100], [800, 600])
+    );
+  }
+
+  #[test]
+  fn spanning_monitors() {
+    assert_eq!(
+      clamp_to_monitors([1500, 100], [800, 600], &MONITORS),
+      ([1500, 100], [800, 600])
+    );
+  }
+
+  #[test]
+  fn partially_off_screen() {
+    assert_eq!(
+      clamp_to_monitors([-200, 900], [800, 600], &MONITORS),
+      ([0, 480], [800, 600])
+    );
+  }
+
+  #[test]
+  fn disconnected_monitor() {
+    assert_eq!(
+      clamp_to_monitors([4000, 200], [800, 600], &MONITORS),
+      ([1120, 200], [800, 600])
+    );
+  }
+
+  #[test]
+  fn larger_than_monitor() {
+    assert_eq!(
+      clamp_to_monitors([2000, -50], [1600, 1200], &MONITORS),
+      ([1920, 0], [1280, 1024])
+    );
+  }
+
+  #[test]
+  fn no_monitor() {
+    assert_eq!(
+      clamp_to_monitors([4000, 200], [800, 600], &[]),
+      ([4000, 200], [800, 600])
+    );
+  }
+}
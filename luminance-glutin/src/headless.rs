@@ -0,0 +1,183 @@
+//! Headless surface, for compute-only workloads.
+
+use crate::GlutinError;
+use glutin::{
+  dpi::PhysicalSize, event_loop::EventLoopWindowTarget, Api, Context, ContextBuilder,
+  ContextCurrentState, GlProfile, GlRequest, NotCurrent, PossiblyCurrent,
+};
+use luminance::context::GraphicsContext;
+use luminance_gl::GL33;
+use std::os::raw::c_void;
+
+/// A surface without any framebuffer, for compute-only workloads.
+///
+/// This is an OpenGL context that is not attached to any window, and that doesn’t allocate any
+/// color, depth or stencil buffer. It is meant for GPGPU work, such as transform feedback or
+/// buffer operations and readback.
+///
+/// As it has no default framebuffer, it has no back buffer and no buffers to swap: unlike
+/// [`GlutinSurface`], it doesn’t provide `back_buffer()` nor `swap_buffers()`. Offscreen
+/// framebuffers can still be created and rendered to.
+///
+/// [`GlutinSurface`]: crate::GlutinSurface
+pub struct GlutinHeadlessSurface {
+  /// The OpenGL context.
+  pub ctx: Context<PossiblyCurrent>,
+  gl: GL33,
+}
+
+unsafe impl GraphicsContext for GlutinHeadlessSurface {
+  type Backend = GL33;
+
+  fn backend(&mut self) -> &mut Self::Backend {
+    debug_assert!(
+      self.ctx.is_current(),
+      "the OpenGL context of the headless surface is not current on this thread"
+    );
+
+    &mut self.gl
+  }
+}
+
+impl GlutinHeadlessSurface {
+  /// Create a new [`GlutinHeadlessSurface`] on the given event loop.
+  ///
+  /// On unix platforms, a surfaceless EGL context is created if the platform supports it;
+  /// otherwise, and on the other platforms, the context is backed by the smallest possible
+  /// offscreen surface, which is never rendered to.
+  pub fn new_gl33<T>(event_loop: &EventLoopWindowTarget<T>) -> Result<Self, GlutinError> {
+    Self::from_context(build_headless(event_loop, ctx_builder())?, true)
+  }
+
+  /// Make a context current and create the surface around it.
+  ///
+  /// The OpenGL function pointers are process-wide: they are only loaded from the context if
+  /// `load_gl` is `true`.
+  fn from_context(ctx: Context<NotCurrent>, load_gl: bool) -> Result<Self, GlutinError> {
+    let ctx = unsafe { ctx.make_current().map_err(|(_, e)| e)? };
+
+    if load_gl {
+      gl::load_with(|s| ctx.get_proc_address(s) as *const c_void);
+    }
+
+    let gl = GL33::new().map_err(GlutinError::GraphicsStateError)?;
+
+    Ok(GlutinHeadlessSurface { ctx, gl })
+  }
+
+  /// Block until all the commands issued with the surface have completed.
+  ///
+  /// When the surface shares objects with other contexts (see [`WorkerContext`]), the changes made
+  /// to the shared objects, such as texture uploads, are only guaranteed to be visible to the
+  /// other contexts once the commands making them have completed, and the objects are bound again
+  /// in the other contexts.
+  pub fn finish(&mut self) {
+    unsafe { gl::Finish() };
+  }
+}
+
+/// An OpenGL context sharing its objects with a [`GlutinSurface`], to be used on a worker thread.
+///
+/// Get one with [`GlutinSurface::create_worker_context`]. The context is not current on any
+/// thread, so it can be sent to a worker thread, which makes it current with
+/// [`WorkerContext::make_current`].
+///
+/// # Threading rules
+///
+/// - An OpenGL context is current on at most one thread, and a thread has at most one current
+///   context. The worker thread must therefore not be the thread of the [`GlutinSurface`], nor of
+///   any other surface.
+/// - Textures and buffers are shared between the contexts, but container objects (framebuffers and
+///   vertex arrays, hence tessellations) are not.
+/// - luminance objects, such as [`Texture`], are tied to the graphics state of the thread that
+///   created them, and cannot be sent to another thread: the objects shared with the worker can
+///   only be handed over at the OpenGL level.
+/// - The changes made on the worker are only visible to the [`GlutinSurface`] once they have
+///   completed: call [`GlutinHeadlessSurface::finish`] on the worker before telling the main thread
+///   that an upload is done.
+/// - The OpenGL function pointers are process-wide, and the worker uses the ones loaded from the
+///   context of the [`GlutinSurface`]. On some platforms (e.g. WGL on Windows), function pointers
+///   are specific to a pixel format: the platform must share objects between the contexts, which
+///   requires compatible pixel formats, for the pointers to be valid for both.
+///
+/// [`GlutinSurface`]: crate::GlutinSurface
+/// [`GlutinSurface::create_worker_context`]: crate::GlutinSurface::create_worker_context
+/// [`Texture`]: luminance::texture::Texture
+pub struct WorkerContext {
+  ctx: Context<NotCurrent>,
+}
+
+impl WorkerContext {
+  /// Create a context sharing its objects with `shared`.
+  pub(crate) fn new<T, C>(
+    event_loop: &EventLoopWindowTarget<T>,
+    shared: &Context<C>,
+  ) -> Result<Self, GlutinError>
+  where
+    C: ContextCurrentState + Clone,
+  {
+    let ctx = build_headless(event_loop, ctx_builder().with_shared_lists(shared))?;
+    Ok(WorkerContext { ctx })
+  }
+
+  /// Make the context current on the calling thread, and wrap it in a [`GlutinHeadlessSurface`].
+  ///
+  /// This must be called on the worker thread, which must not have any other current context.
+  pub fn make_current(self) -> Result<GlutinHeadlessSurface, GlutinError> {
+    // keep the function pointers loaded by the surface, which the main thread is using
+    GlutinHeadlessSurface::from_context(self.ctx, false)
+  }
+}
+
+/// Context builder without any buffer.
+fn ctx_builder<'a>() -> ContextBuilder<'a, NotCurrent> {
+  ContextBuilder::new()
+    .with_gl(GlRequest::Specific(Api::OpenGl, (3, 3)))
+    .with_gl_profile(GlProfile::Core)
+    .with_pixel_format(0, 0)
+    .with_depth_buffer(0)
+    .with_stencil_buffer(0)
+    .with_double_buffer(Some(false))
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+fn build_headless<T, C>(
+  event_loop: &EventLoopWindowTarget<T>,
+  builder: ContextBuilder<'_, C>,
+) -> Result<Context<NotCurrent>, GlutinError>
+where
+  C: ContextCurrentState + Clone,
+{
+  use glutin::platform::unix::HeadlessContextExt;
+
+  builder
+    .clone()
+    .build_surfaceless(event_loop)
+    .or_else(|_| builder.build_headless(event_loop, PhysicalSize::new(1, 1)))
+    .map_err(GlutinError::CreationError)
+}
+
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+fn build_headless<T, C>(
+  event_loop: &EventLoopWindowTarget<T>,
+  builder: ContextBuilder<'_, C>,
+) -> Result<Context<NotCurrent>, GlutinError>
+where
+  C: ContextCurrentState,
+{
+  builder
+    .build_headless(event_loop, PhysicalSize::new(1, 1))
+    .map_err(GlutinError::CreationError)
+}
@@ -0,0 +1,152 @@
+//! Input helpers.
+//!
+//! Those types collect window events into state that is easier to query than the raw events.
+
+use glutin::event::{TouchPhase, WindowEvent};
+use std::path::PathBuf;
+
+/// Drag and drop target collecting the files dropped on a window.
+///
+/// Feed it the window events with [`DropTarget::handle_event`], then query the files being dragged
+/// over the window with [`DropTarget::hovered_files`] and take the dropped ones with
+/// [`DropTarget::take_dropped_files`].
+#[derive(Clone, Debug, Default)]
+pub struct DropTarget {
+  hovered: Vec<PathBuf>,
+  dropped: Vec<PathBuf>,
+}
+
+impl DropTarget {
+  /// Create an empty drop target.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Handle a window event.
+  ///
+  /// Returns `true` if the event was a drag and drop event, in which case it was consumed; other
+  /// events are ignored.
+  pub fn handle_event(&mut self, event: &WindowEvent) -> bool {
+    match event {
+      WindowEvent::HoveredFile(path) => {
+        self.hovered.push(path.clone());
+        true
+      }
+
+      WindowEvent::HoveredFileCancelled => {
+        self.hovered.clear();
+        true
+      }
+
+      WindowEvent::DroppedFile(path) => {
+        // files hovered together are dropped together, one event per file
+        self.hovered.retain(|hovered| hovered != path);
+        self.dropped.push(path.clone());
+        true
+      }
+
+      _ => false,
+    }
+  }
+
+  /// Files currently dragged over the window.
+  ///
+  /// Some platforms (e.g. Wayland) don’t report hovered files, so this might stay empty until the
+  /// files are dropped.
+  pub fn hovered_files(&self) -> &[PathBuf] {
+    &self.hovered
+  }
+
+  /// Check whether files have been dropped and not taken yet.
+  pub fn has_dropped_files(&self) -> bool {
+    !self.dropped.is_empty()
+  }
+
+  /// Take the files dropped since the last call, in the order they were dropped.
+  pub fn take_dropped_files(&mut self) -> Vec<PathBuf> {
+    std::mem::take(&mut self.dropped)
+  }
+}
+
+/// A touch point in contact with a window.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TouchPoint {
+  /// Identifier of the touch point, unique among the touch points in contact with the window.
+  ///
+  /// Identifiers might be reused once a touch point is lifted.
+  pub id: u64,
+  /// Position of the touch point, in physical pixels, relative to the top-left corner of the
+  /// client area of the window.
+  pub position: [f64; 2],
+  /// Position where the touch point started.
+  pub start_position: [f64; 2],
+}
+
+/// Touch state collecting the touch points in contact with a window.
+///
+/// Feed it the window events with [`TouchState::handle_event`], then query the touch points with
+/// [`TouchState::points`] and [`TouchState::point`].
+#[derive(Clone, Debug, Default)]
+pub struct TouchState {
+  points: Vec<TouchPoint>,
+}
+
+impl TouchState {
+  /// Create a touch state without any touch point.
+  pub fn new() -> Self {
+    Self::default()
+  }
+
+  /// Handle a window event.
+  ///
+  /// Returns `true` if the event was a touch event, in which case it was consumed; other events
+  /// are ignored.
+  pub fn handle_event(&mut self, event: &WindowEvent) -> bool {
+    let touch = match event {
+      WindowEvent::Touch(touch) => touch,
+      _ => return false,
+    };
+    let position = [touch.location.x, touch.location.y];
+
+    match touch.phase {
+      TouchPhase::Started => {
+        // a point started twice (e.g. its end was missed) restarts
+        self.points.retain(|point| point.id != touch.id);
+        self.points.push(TouchPoint {
+          id: touch.id,
+          position,
+          start_position: position,
+        });
+      }
+
+      TouchPhase::Moved => {
+        if let Some(point) = self.points.iter_mut().find(|point| point.id == touch.id) {
+          point.position = position;
+        }
+      }
+
+      TouchPhase::Ended | TouchPhase::Cancelled => {
+        self.points.retain(|point| point.id != touch.id);
+      }
+    }
+
+    true
+  }
+
+  /// Touch points in contact with the window, in the order they started.
+  pub fn points(&self) -> &[TouchPoint] {
+    &self.points
+  }
+
+  /// Get the touch point with the given identifier, if in contact with the window.
+  pub fn point(&self, id: u64) -> Option<&TouchPoint> {
+    self.points.iter().find(|point| point.id == id)
+  }
+
+  /// Forget all the touch points, e.g. when the window loses the focus without reporting that
+  /// its touch points were lifted.
+  pub fn clear(&mut self) {
+    self.points.clear();
+  }
+}
@@ -2,19 +2,72 @@
 
 #![deny(missing_docs)]
 
-use gl;
+mod app;
+mod backdrop;
+mod builder;
+mod geometry;
+mod headless;
+#[cfg(feature = "input")]
+mod input;
+mod present;
+mod quirks;
+mod swap_control;
+mod sync_control;
+mod taskbar;
+mod timing;
+mod viewport;
+mod windowing;
+
+pub use crate::app::{AppCallbacks, AppExit, GlutinApp};
+pub use crate::backdrop::Backdrop;
+pub use crate::builder::{ExactFormat, GlutinSurfaceBuilder, ResetNotification};
+pub use crate::geometry::WindowGeometry;
+pub use crate::headless::{GlutinHeadlessSurface, WorkerContext};
+#[cfg(feature = "input")]
+pub use crate::input::{DropTarget, TouchPoint, TouchState};
+pub use crate::quirks::QuirkSet;
+pub use crate::swap_control::SwapControl;
+pub use crate::timing::SwapTimingStats;
+pub use crate::viewport::SplitLayout;
+pub use crate::windowing::WindowingBackend;
+
+use crate::timing::{FrameBudget, FrameLimiter, PresentationClock, SwapTiming};
 use glutin::{
-  event_loop::EventLoop, window::WindowBuilder, Api, ContextBuilder, ContextError, CreationError,
-  GlProfile, GlRequest, NotCurrent, PossiblyCurrent, WindowedContext,
+  dpi::{PhysicalPosition, PhysicalSize},
+  error::ExternalError,
+  event::{ElementState, Event, MouseButton, StartCause, WindowEvent},
+  event_loop::{EventLoop, EventLoopWindowTarget},
+  monitor::MonitorHandle,
+  window::{Theme, Window, WindowBuilder},
+  Api, ContextBuilder, ContextError, CreationError, GlProfile, GlRequest, NotCurrent, PixelFormat,
+  PixelFormatRequirements, PossiblyCurrent, WindowedContext,
 };
+use luminance::backend::color_slot::ColorSlot;
+use luminance::backend::depth_stencil_slot::DepthStencilSlot;
 use luminance::context::GraphicsContext;
 use luminance::framebuffer::{Framebuffer, FramebufferError};
-use luminance::texture::Dim2;
+use luminance::pipeline::{PipelineError, PipelineState, Viewport};
+use luminance::pixel::Pixel;
+use luminance::pixel::{Depth32F, NormRGBA8UI};
+use luminance::render_state::RenderState;
+use luminance::scissor::ScissorRegion;
+use luminance::shader::ProgramError;
+use luminance::tess::Mode;
+use luminance::tess::TessError;
+use luminance::texture::{Dim2, MinFilter, Sampler, Texture, TextureError};
 pub use luminance_gl::gl33::StateQueryError;
 use luminance_gl::GL33;
+use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+use std::any::{Any, TypeId};
+use std::cell::Cell;
+#[cfg(feature = "clipboard")]
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::error;
+use std::ffi::CStr;
 use std::fmt;
-use std::os::raw::c_void;
+use std::os::raw::{c_char, c_void};
+use std::ptr;
 
 /// Error that might occur when creating a Glutin surface.
 #[derive(Debug)]
@@ -26,6 +79,65 @@ pub enum GlutinError {
   ContextError(ContextError),
   /// Graphics state error that might occur when querying the initial state.
   GraphicsStateError(StateQueryError),
+  /// The platform couldn’t provide the exact pixel format that was requested.
+  ExactFormatUnavailable {
+    /// Requested pixel format.
+    requested: ExactFormat,
+    /// Pixel format the platform provided instead.
+    obtained: PixelFormat,
+  },
+  /// The GPU ran out of memory.
+  ///
+  /// This is reported when `GL_OUT_OF_MEMORY` is raised by the commands of a frame. The state of
+  /// the OpenGL objects involved is undefined; you might want to release some resources (caches,
+  /// etc.) and render again, but recovery is not guaranteed.
+  OutOfMemory,
+  /// OpenGL errors were raised by the commands of a frame.
+  ///
+  /// The carried codes (e.g. `GL_INVALID_OPERATION`) are the pending errors, in the order OpenGL
+  /// reported them; they usually point at a misuse of raw OpenGL code or a driver issue.
+  /// `GL_OUT_OF_MEMORY` is reported as [`GlutinError::OutOfMemory`] instead.
+  ///
+  /// [`GlutinSurface::swap_buffers`] reports this error after swapping the buffers: the frame was
+  /// presented anyway.
+  GlError(Vec<gl::types::GLenum>),
+  /// Waiting for the GPU to complete a frame failed.
+  ///
+  /// The fence marking the end of the frame couldn’t be created, or waiting for it failed
+  /// (`GL_WAIT_FAILED`), which typically means that the context was lost. See
+  /// [`GlutinSurface::render_frame_blocking`].
+  FenceFailed,
+  /// The event loop was about to be created outside of the main thread, which the platform doesn’t
+  /// allow.
+  ///
+  /// Create the surface on the main thread, or create the event loop yourself and use
+  /// [`GlutinSurface::new_gl33_with_event_loop`].
+  NotMainThread,
+  /// A framebuffer the surface relies on couldn’t be created.
+  FramebufferError(FramebufferError),
+  /// The platform doesn’t provide any pixel format matching the requested one.
+  ///
+  /// The carried requirements are the ones the pixel format was selected with, so that you can
+  /// see which of them could not be satisfied (e.g. too many samples). `glutin-0.28` doesn’t
+  /// expose the list of pixel formats the platform offers, so they cannot be reported.
+  NoConfigAvailable {
+    /// Requested pixel format requirements.
+    requested: PixelFormatRequirements,
+  },
+  /// A shader program the surface relies on couldn’t be created.
+  ProgramError(ProgramError),
+  /// A tessellation the surface relies on couldn’t be created.
+  TessError(TessError),
+  /// A pipeline run by the surface failed.
+  PipelineError(PipelineError),
+  /// The OpenGL context doesn’t support GLSL 3.30, which the GL33 backend requires.
+  ///
+  /// Some drivers create OpenGL 3.3 contexts with an older (or unparsable) GLSL version; shaders
+  /// would fail to compile.
+  UnsupportedGlslVersion {
+    /// GLSL version string reported by the driver (`GL_SHADING_LANGUAGE_VERSION`).
+    version: String,
+  },
 }
 
 impl fmt::Display for GlutinError {
@@ -36,6 +148,33 @@ impl fmt::Display for GlutinError {
       GlutinError::GraphicsStateError(ref e) => {
         write!(f, "OpenGL graphics state initialization error: {}", e)
       }
+      GlutinError::ExactFormatUnavailable {
+        ref requested,
+        ref obtained,
+      } => write!(
+        f,
+        "exact pixel format unavailable: requested {:?}, obtained {:?}",
+        requested, obtained
+      ),
+      GlutinError::OutOfMemory => f.write_str("GPU out of memory"),
+      GlutinError::GlError(ref errors) => write!(f, "OpenGL errors: {:#x?}", errors),
+      GlutinError::FenceFailed => f.write_str("waiting for the GPU to complete the frame failed"),
+      GlutinError::NotMainThread => f.write_str(
+        "the event loop must be created on the main thread on this platform; create the surface on \
+         the main thread or provide your own event loop",
+      ),
+      GlutinError::FramebufferError(ref e) => write!(f, "framebuffer error: {}", e),
+      GlutinError::NoConfigAvailable { ref requested } => write!(
+        f,
+        "no pixel format available for the requested requirements: {:?}",
+        requested
+      ),
+      GlutinError::ProgramError(ref e) => write!(f, "shader program error: {}", e),
+      GlutinError::TessError(ref e) => write!(f, "tessellation error: {}", e),
+      GlutinError::PipelineError(ref e) => write!(f, "pipeline error: {}", e),
+      GlutinError::UnsupportedGlslVersion { ref version } => {
+        write!(f, "unsupported GLSL version (3.30 required): {}", version)
+      }
     }
   }
 }
@@ -46,6 +185,41 @@ impl error::Error for GlutinError {
       GlutinError::CreationError(e) => Some(e),
       GlutinError::ContextError(e) => Some(e),
       GlutinError::GraphicsStateError(e) => Some(e),
+      GlutinError::ExactFormatUnavailable { .. } => None,
+      GlutinError::OutOfMemory => None,
+      GlutinError::GlError(_) => None,
+      GlutinError::FenceFailed => None,
+      GlutinError::NotMainThread => None,
+      GlutinError::FramebufferError(e) => Some(e),
+      GlutinError::NoConfigAvailable { .. } => None,
+      GlutinError::ProgramError(e) => Some(e),
+      GlutinError::TessError(e) => Some(e),
+      GlutinError::PipelineError(e) => Some(e),
+      GlutinError::UnsupportedGlslVersion { .. } => None,
+    }
+  }
+}
+
+impl GlutinError {
+  /// Turn an error that occurred while creating a context with the given pixel format
+  /// requirements into a [`GlutinError`], reporting the requirements if they could not be
+  /// satisfied.
+  fn from_creation_error(e: CreationError, requested: PixelFormatRequirements) -> Self {
+    let no_pixel_format = match e {
+      CreationError::NoAvailablePixelFormat => true,
+      CreationError::CreationErrors(ref errors) => {
+        !errors.is_empty()
+          && errors
+            .iter()
+            .all(|e| matches!(**e, CreationError::NoAvailablePixelFormat))
+      }
+      _ => false,
+    };
+
+    if no_pixel_format {
+      GlutinError::NoConfigAvailable { requested }
+    } else {
+      GlutinError::CreationError(e)
     }
   }
 }
@@ -62,32 +236,516 @@ impl From<ContextError> for GlutinError {
   }
 }
 
+impl From<FramebufferError> for GlutinError {
+  fn from(e: FramebufferError) -> Self {
+    GlutinError::FramebufferError(e)
+  }
+}
+
 impl From<StateQueryError> for GlutinError {
   fn from(e: StateQueryError) -> Self {
     GlutinError::GraphicsStateError(e)
   }
 }
 
+impl From<ProgramError> for GlutinError {
+  fn from(e: ProgramError) -> Self {
+    GlutinError::ProgramError(e)
+  }
+}
+
+impl From<TessError> for GlutinError {
+  fn from(e: TessError) -> Self {
+    GlutinError::TessError(e)
+  }
+}
+
+impl From<PipelineError> for GlutinError {
+  fn from(e: PipelineError) -> Self {
+    GlutinError::PipelineError(e)
+  }
+}
+
+/// Level of a window, relative to other windows.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WindowLevel {
+  /// The window is stacked like any other window.
+  Normal,
+  /// The window is always on top of other windows.
+  AlwaysOnTop,
+}
+
+/// Eye of a stereo back buffer; see [`GlutinSurface::back_buffer_stereo`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum StereoEye {
+  /// Left back buffer (`GL_BACK_LEFT`).
+  Left,
+  /// Right back buffer (`GL_BACK_RIGHT`).
+  Right,
+}
+
+/// Color buffers of the default framebuffer drawn to; see [`GlutinSurface::set_draw_buffer`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum DrawBuffer {
+  /// Back buffers (`GL_BACK`); both the left and right ones with a stereo default framebuffer.
+  Back,
+  /// Front buffers (`GL_FRONT`), i.e. the ones being displayed.
+  Front,
+  /// Left back buffer (`GL_BACK_LEFT`).
+  BackLeft,
+  /// Right back buffer (`GL_BACK_RIGHT`), only with a stereo default framebuffer.
+  BackRight,
+}
+
+impl DrawBuffer {
+  fn to_gl(self) -> gl::types::GLenum {
+    match self {
+      DrawBuffer::Back => gl::BACK,
+      DrawBuffer::Front => gl::FRONT,
+      DrawBuffer::BackLeft => gl::BACK_LEFT,
+      DrawBuffer::BackRight => gl::BACK_RIGHT,
+    }
+  }
+}
+
+/// Color buffer of the default framebuffer read from; see [`GlutinSurface::set_read_buffer`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum ReadBuffer {
+  /// Back buffer (`GL_BACK`); the left one with a stereo default framebuffer.
+  Back,
+  /// Front buffer (`GL_FRONT`), i.e. the one being displayed; the left one with a stereo default
+  /// framebuffer.
+  Front,
+  /// Left back buffer (`GL_BACK_LEFT`).
+  BackLeft,
+  /// Right back buffer (`GL_BACK_RIGHT`), only with a stereo default framebuffer.
+  BackRight,
+}
+
+impl ReadBuffer {
+  fn to_gl(self) -> gl::types::GLenum {
+    match self {
+      ReadBuffer::Back => gl::BACK,
+      ReadBuffer::Front => gl::FRONT,
+      ReadBuffer::BackLeft => gl::BACK_LEFT,
+      ReadBuffer::BackRight => gl::BACK_RIGHT,
+    }
+  }
+}
+
+/// Implementation limits of an OpenGL context.
+///
+/// Those are queried when the surface is created; you can get them with
+/// [`GlutinSurface::limits`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct GlLimits {
+  /// Maximum width and height of 1D and 2D textures (`GL_MAX_TEXTURE_SIZE`).
+  pub max_texture_size: u32,
+  /// Maximum width, height and depth of 3D textures (`GL_MAX_3D_TEXTURE_SIZE`).
+  pub max_3d_texture_size: u32,
+  /// Maximum width and height of cubemap faces (`GL_MAX_CUBE_MAP_TEXTURE_SIZE`).
+  pub max_cube_map_texture_size: u32,
+  /// Maximum number of layers of array textures (`GL_MAX_ARRAY_TEXTURE_LAYERS`).
+  pub max_array_texture_layers: u32,
+  /// Maximum number of textures a fragment shader can use (`GL_MAX_TEXTURE_IMAGE_UNITS`).
+  pub max_texture_image_units: u32,
+  /// Maximum number of textures all the shader stages can use together
+  /// (`GL_MAX_COMBINED_TEXTURE_IMAGE_UNITS`).
+  pub max_combined_texture_image_units: u32,
+  /// Maximum width and height of renderbuffers (`GL_MAX_RENDERBUFFER_SIZE`).
+  pub max_renderbuffer_size: u32,
+  /// Maximum number of color attachments of a framebuffer (`GL_MAX_COLOR_ATTACHMENTS`).
+  pub max_color_attachments: u32,
+  /// Maximum number of draw buffers (`GL_MAX_DRAW_BUFFERS`).
+  pub max_draw_buffers: u32,
+  /// Maximum number of samples of multisample framebuffers (`GL_MAX_SAMPLES`).
+  pub max_samples: u32,
+  /// Maximum number of vertex attributes (`GL_MAX_VERTEX_ATTRIBS`).
+  pub max_vertex_attribs: u32,
+  /// Maximum size, in bytes, of uniform blocks (`GL_MAX_UNIFORM_BLOCK_SIZE`).
+  pub max_uniform_block_size: u32,
+  /// Maximum width and height of the viewport (`GL_MAX_VIEWPORT_DIMS`).
+  pub max_viewport_dims: [u32; 2],
+}
+
+impl GlLimits {
+  /// Query the limits of the current OpenGL context.
+  fn query() -> Self {
+    let get = |name| {
+      let mut value = 0;
+      unsafe { gl::GetIntegerv(name, &mut value) };
+      value.max(0) as u32
+    };
+
+    let mut max_viewport_dims = [0; 2];
+    unsafe { gl::GetIntegerv(gl::MAX_VIEWPORT_DIMS, max_viewport_dims.as_mut_ptr()) };
+
+    GlLimits {
+      max_texture_size: get(gl::MAX_TEXTURE_SIZE),
+      max_3d_texture_size: get(gl::MAX_3D_TEXTURE_SIZE),
+      max_cube_map_texture_size: get(gl::MAX_CUBE_MAP_TEXTURE_SIZE),
+      max_array_texture_layers: get(gl::MAX_ARRAY_TEXTURE_LAYERS),
+      max_texture_image_units: get(gl::MAX_TEXTURE_IMAGE_UNITS),
+      max_combined_texture_image_units: get(gl::MAX_COMBINED_TEXTURE_IMAGE_UNITS),
+      max_renderbuffer_size: get(gl::MAX_RENDERBUFFER_SIZE),
+      max_color_attachments: get(gl::MAX_COLOR_ATTACHMENTS),
+      max_draw_buffers: get(gl::MAX_DRAW_BUFFERS),
+      max_samples: get(gl::MAX_SAMPLES),
+      max_vertex_attribs: get(gl::MAX_VERTEX_ATTRIBS),
+      max_uniform_block_size: get(gl::MAX_UNIFORM_BLOCK_SIZE),
+      max_viewport_dims: max_viewport_dims.map(|v| v.max(0) as u32),
+    }
+  }
+}
+
+/// Outcome of presenting a frame with [`GlutinSurface::swap_buffers`].
+///
+/// Each variant documents the glutin errors it is mapped from, and the recommended response.
+#[must_use]
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SwapResult {
+  /// The frame was presented.
+  Ok,
+  /// The frame was presented, but the surface was resized while it was rendered (see
+  /// [`GlutinSurface::is_resized_since_back_buffer`]), so it was rendered with an outdated size.
+  ///
+  /// Keep rendering: the next frame gets a back buffer of the new size. Frames that are expensive
+  /// to render can be rendered again right away.
+  Suboptimal,
+  /// The frame couldn’t be presented because the window surface is gone.
+  ///
+  /// This is mapped from the OS errors glutin reports when swapping ([`ContextError::OsError`] and
+  /// [`ContextError::IoError`]), e.g. an X11 error such as `BadDrawable` raised by
+  /// `glXSwapBuffers`, or `SwapBuffers` failing on Windows. The OpenGL context itself is still
+  /// alive, but the window is unusable: recreate the surface with [`GlutinSurface::recreate`].
+  ///
+  /// [`ContextError::OsError`]: glutin::ContextError::OsError
+  /// [`ContextError::IoError`]: glutin::ContextError::IoError
+  SurfaceLost,
+  /// The frame couldn’t be presented because the OpenGL context was lost, typically after a GPU
+  /// reset (see [`GlutinSurfaceBuilder::with_reset_notification`]).
+  ///
+  /// This is mapped from [`ContextError::ContextLost`], which glutin reports when
+  /// `eglSwapBuffers` fails with `EGL_CONTEXT_LOST`. All the resources of the surface are gone:
+  /// drop them, recreate the surface with [`GlutinSurface::recreate`], and create them again.
+  ///
+  /// [`ContextError::ContextLost`]: glutin::ContextError::ContextLost
+  ContextLost,
+}
+
+/// Outcome of handling a window event with [`GlutinSurface::handle_window_event`] or
+/// [`GlutinSurface::handle_event`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SurfaceEventOutcome {
+  /// The surface acted on the event, so it doesn’t need further handling for the surface’s sake.
+  pub handled: bool,
+  /// The content of the window is outdated and a new frame should be rendered.
+  pub redraw_needed: bool,
+  /// The user asked to close the window, and the close handler, if any, didn’t veto it (see
+  /// [`GlutinSurface::set_close_handler`]).
+  pub close_requested: bool,
+  /// The window moved to another monitor, and the surface was reconfigured (see
+  /// [`GlutinSurface::auto_reconfigure_on_monitor_change`]).
+  pub monitor_changed: bool,
+  /// A frame should be rendered and presented now (see [`GlutinSurface::handle_event`]).
+  pub render_now: bool,
+  /// The theme of the window changed (see [`GlutinSurface::theme`]).
+  pub theme_changed: bool,
+}
+
+impl SurfaceEventOutcome {
+  /// Merge the outcome of another event into this one.
+  fn merge(&mut self, other: SurfaceEventOutcome) {
+    self.handled |= other.handled;
+    self.redraw_needed |= other.redraw_needed;
+    self.close_requested |= other.close_requested;
+    self.monitor_changed |= other.monitor_changed;
+    self.render_now |= other.render_now;
+    self.theme_changed |= other.theme_changed;
+  }
+}
+
+/// Vertical synchronization of buffer swaps.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Vsync {
+  /// Buffers are swapped as soon as possible, which might cause tearing.
+  Off,
+  /// Buffers are swapped in sync with the refresh rate of the monitor.
+  On,
+}
+
+impl Vsync {
+  fn from_bool(vsync: bool) -> Self {
+    if vsync {
+      Vsync::On
+    } else {
+      Vsync::Off
+    }
+  }
+}
+
+/// Presentation mode of a surface, i.e. how buffer swaps are synchronized with the monitor.
+///
+/// OpenGL has no presentation modes: they are emulated with the swap interval. See
+/// [`GlutinSurface::set_present_mode`].
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum PresentMode {
+  /// Buffers are swapped as soon as possible, which might cause tearing (swap interval `0`).
+  Immediate,
+  /// Buffers are swapped in sync with the refresh rate of the monitor (swap interval `1`).
+  Fifo,
+  /// Like [`PresentMode::Fifo`], but late frames are swapped immediately instead of waiting for
+  /// the next refresh, which might cause tearing (adaptive vsync, swap interval `-1`).
+  FifoRelaxed,
+  /// Buffers are swapped in sync with the refresh rate of the monitor, without making the
+  /// application wait for it.
+  ///
+  /// OpenGL cannot control the number of buffers of the swap chain, so this is never effective; it
+  /// falls back to [`PresentMode::Fifo`], which doesn’t tear either.
+  Mailbox,
+}
+
+impl From<Vsync> for PresentMode {
+  fn from(vsync: Vsync) -> Self {
+    match vsync {
+      Vsync::Off => PresentMode::Immediate,
+      Vsync::On => PresentMode::Fifo,
+    }
+  }
+}
+
+/// Flags of an OpenGL context.
+///
+/// Those are the flags actually obtained from the platform, which might differ from the requested
+/// ones. You can get them with [`GlutinSurface::context_flags`].
+///
+/// Forward-compatible contexts cannot be explicitly requested with `glutin-0.28`, which decides
+/// on its own (they are for instance always requested on macOS).
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct ContextFlags {
+  /// The context is forward-compatible: deprecated functionality is removed.
+  pub forward_compatible: bool,
+  /// The context is a debug context.
+  pub debug: bool,
+  /// The context has robust buffer access.
+  pub robust_access: bool,
+}
+
+impl ContextFlags {
+  /// Query the flags of the current OpenGL context.
+  fn query() -> Self {
+    let mut flags = 0;
+    unsafe { gl::GetIntegerv(gl::CONTEXT_FLAGS, &mut flags) };
+    let flags = flags as gl::types::GLenum;
+
+    ContextFlags {
+      forward_compatible: flags & gl::CONTEXT_FLAG_FORWARD_COMPATIBLE_BIT != 0,
+      debug: flags & gl::CONTEXT_FLAG_DEBUG_BIT != 0,
+      robust_access: flags & gl::CONTEXT_FLAG_ROBUST_ACCESS_BIT != 0,
+    }
+  }
+}
+
+/// GPU memory usage.
+///
+/// See [`GlutinSurface::gpu_memory_info`].
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct GpuMemoryInfo {
+  /// Total amount of dedicated GPU memory, in kilobytes, if the driver reports it.
+  pub total_kb: Option<u32>,
+  /// Amount of GPU memory currently available, in kilobytes.
+  pub available_kb: u32,
+}
+
+// GL_NVX_gpu_memory_info
+const GPU_MEMORY_INFO_TOTAL_AVAILABLE_MEMORY_NVX: gl::types::GLenum = 0x9048;
+const GPU_MEMORY_INFO_CURRENT_AVAILABLE_VIDMEM_NVX: gl::types::GLenum = 0x9049;
+
+// GL_ATI_meminfo
+const TEXTURE_FREE_MEMORY_ATI: gl::types::GLenum = 0x87FC;
+
+// GL_EXT_texture_filter_anisotropic, GL_ARB_texture_filter_anisotropic
+const MAX_TEXTURE_MAX_ANISOTROPY: gl::types::GLenum = 0x84FF;
+
+/// Time waited for a fence at once, in nanoseconds, before checking again.
+const FENCE_WAIT_TIMEOUT_NS: u64 = 1_000_000_000;
+
+/// Maximum number of pending OpenGL errors taken at once.
+const MAX_PENDING_GL_ERRORS: usize = 32;
+
+/// Parameters a surface was created with, kept to recreate it.
+#[derive(Clone, Debug)]
+struct CreationParams {
+  window_builder: WindowBuilder,
+  ctx_builder: ContextBuilder<'static, NotCurrent>,
+}
+
+impl CreationParams {
+  /// Record the parameters of a surface.
+  ///
+  /// Context sharing is not recorded, as the shared context might not outlive the surface.
+  fn new(window_builder: &WindowBuilder, ctx_builder: &ContextBuilder<'_, NotCurrent>) -> Self {
+    let mut unshared = ContextBuilder::new();
+    unshared.pf_reqs = ctx_builder.pf_reqs.clone();
+    unshared.gl_attr.version = ctx_builder.gl_attr.version;
+    unshared.gl_attr.profile = ctx_builder.gl_attr.profile;
+    unshared.gl_attr.debug = ctx_builder.gl_attr.debug;
+    unshared.gl_attr.robustness = ctx_builder.gl_attr.robustness;
+    unshared.gl_attr.vsync = ctx_builder.gl_attr.vsync;
+
+    CreationParams {
+      window_builder: window_builder.clone(),
+      ctx_builder: unshared,
+    }
+  }
+}
+
+/// Filter of window events; see [`GlutinSurface::set_event_filter`].
+type EventFilter = dyn FnMut(&WindowEvent) -> bool;
+
+/// Render target of [`GlutinSurface::frame`].
+pub type RenderTarget = Framebuffer<GL33, Dim2, NormRGBA8UI, Depth32F>;
+
 /// The Glutin surface.
 ///
 /// You want to create such an object in order to use any [luminance] construct.
 ///
+/// # Thread affinity
+///
+/// An OpenGL context is current on at most one thread at a time, and OpenGL calls apply to the
+/// context current on the calling thread. The surface cannot be sent to another thread: it stays
+/// on the thread that created it, where its context is made current. Its context can still stop
+/// being current on that thread, when another context (of another library, or a raw glutin
+/// context) is made current in the meantime; call [`GlutinSurface::make_current`] before using
+/// the surface again. [`GlutinSurface::current_thread_owns_context`] tells whether the context is
+/// current on the calling thread.
+///
+/// Objects created with the surface (buffers, textures, framebuffers, etc.) cannot be sent to
+/// other threads either; use a [`WorkerContext`] to work with OpenGL on another thread.
+///
 /// [luminance]: https://crates.io/crates/luminance
 pub struct GlutinSurface {
+  /// Scratch framebuffers, indexed by the type of framebuffer.
+  ///
+  /// Declared first so that they are dropped while the OpenGL context is still alive.
+  scratch_framebuffers: HashMap<TypeId, Box<dyn Any>>,
+  /// Resources of the presentation pass of `GlutinSurface::frame` and
+  /// `GlutinSurface::present_texture`; dropped before the context, too.
+  presenter: present::Presenter,
   /// The windowed context.
   pub ctx: WindowedContext<PossiblyCurrent>,
   /// OpenGL 3.3 state.
   gl: GL33,
+  /// GLSL version of the OpenGL context.
+  glsl_version: (u16, u16),
+  /// Known bugs of the driver.
+  quirks: QuirkSet,
+  /// Flags of the OpenGL context.
+  context_flags: ContextFlags,
+  /// Extensions supported by the OpenGL context.
+  extensions: HashSet<String>,
+  /// Implementation limits of the OpenGL context.
+  limits: GlLimits,
+  /// Parameters the surface was created with.
+  params: CreationParams,
+  /// Scale of the render target of [`GlutinSurface::frame`], relative to the surface.
+  render_scale: f32,
+  /// Render target of [`GlutinSurface::frame`]; lazily created.
+  render_target: Option<RenderTarget>,
+  /// Number of times the surface was resized.
+  size_version: u64,
+  /// Size version when the back buffer or the render target of [`GlutinSurface::frame`] was last
+  /// obtained.
+  captured_size_version: u64,
+  /// Monitor the window was last seen on, if monitor changes are tracked.
+  monitor: Option<Option<MonitorHandle>>,
+  /// Current presentation mode.
+  present_mode: PresentMode,
+  /// Swap interval control mechanism forced by the user, if any.
+  forced_swap_control: Option<SwapControl>,
+  /// Swap interval control mechanism that last changed the swap interval, if any.
+  swap_control: Option<SwapControl>,
+  /// Default clear color and depth, if set.
+  default_clear: Option<([f32; 4], f32)>,
+  /// Whether the back buffer is cleared when the surface is resized.
+  clear_on_resize: bool,
+  /// Whether resize events are coalesced until [`GlutinSurface::commit_pending_resize`].
+  coalesce_resizes: bool,
+  /// Latest size reported by a resize event and not applied yet, if resizes are coalesced.
+  pending_resize: Option<[u32; 2]>,
+  /// Color buffers of the default framebuffer selected for drawing.
+  draw_buffer: DrawBuffer,
+  /// Color buffer of the default framebuffer selected for reading.
+  read_buffer: ReadBuffer,
+  /// Timing of buffer swaps, if enabled.
+  swap_timing: Option<SwapTiming>,
+  /// Clock sampled when buffers are swapped.
+  presentation_clock: PresentationClock,
+  /// Time spent in the last buffer swap.
+  present_latency: std::time::Duration,
+  /// Frame rate cap applied while the window is in the background, if any.
+  background_fps: Option<u32>,
+  /// Frame limiter applying the background frame rate cap.
+  frame_limiter: FrameLimiter,
+  /// Frame budget, if set.
+  frame_budget: Option<FrameBudget>,
+  /// Stack of scissor regions; the last one is the current one.
+  scissor_stack: Vec<ScissorRegion>,
+  /// Regions of the window dragging it when clicked, as `[x, y, width, height]`.
+  drag_regions: Vec<[u32; 4]>,
+  /// Last known position of the cursor in the window, if it is in the window.
+  cursor_position: Option<PhysicalPosition<f64>>,
+  /// Filter run on window events before the surface handles them.
+  event_filter: Option<Box<EventFilter>>,
+  /// Handler deciding whether close requests go through.
+  close_handler: Option<Box<dyn FnMut() -> bool>>,
+  /// Files dropped on the window.
+  #[cfg(feature = "input")]
+  drop_target: DropTarget,
+  /// Touch points in contact with the window.
+  #[cfg(feature = "input")]
+  touch_state: TouchState,
+  /// Whether the window is currently decorated.
+  decorated: bool,
+  /// Whether the window is currently minimized, as far as we know.
+  minimized: Cell<bool>,
+  /// Whether the window currently has the input focus.
+  focused: bool,
+  /// Theme of the window, if known.
+  theme: Option<Theme>,
+  /// System clipboard; lazily initialized.
+  #[cfg(feature = "clipboard")]
+  clipboard: RefCell<Option<arboard::Clipboard>>,
 }
 
 unsafe impl GraphicsContext for GlutinSurface {
   type Backend = GL33;
 
   fn backend(&mut self) -> &mut Self::Backend {
+    debug_assert!(
+      self.ctx.is_current(),
+      "the OpenGL context of the surface is not current; call GlutinSurface::make_current first"
+    );
+
     &mut self.gl
   }
 }
 
+unsafe impl HasRawWindowHandle for GlutinSurface {
+  fn raw_window_handle(&self) -> RawWindowHandle {
+    GlutinSurface::raw_window_handle(self)
+  }
+}
+
 impl GlutinSurface {
   /// Create a new [`GlutinSurface`] by consuming a [`WindowBuilder`].
   ///
@@ -107,27 +765,24 @@ impl GlutinSurface {
     CB:
       FnOnce(&mut EventLoop<()>, ContextBuilder<'a, NotCurrent>) -> ContextBuilder<'a, NotCurrent>,
   {
-    let mut event_loop = EventLoop::new();
+    let mut event_loop = new_event_loop()?;
 
     let window_builder = window_builder(&mut event_loop, WindowBuilder::new());
 
-    let windowed_ctx = ctx_builder(
+    let ctx_builder = ctx_builder(
       &mut event_loop,
       ContextBuilder::new()
         .with_gl(GlRequest::Specific(Api::OpenGl, (3, 3)))
         .with_gl_profile(GlProfile::Core),
-    )
-    .build_windowed(window_builder, &event_loop)?;
+    );
+    let params = CreationParams::new(&window_builder, &ctx_builder);
+    let requested = ctx_builder.pf_reqs.clone();
+    let windowed_ctx = ctx_builder
+      .build_windowed(window_builder, &event_loop)
+      .map_err(|e| GlutinError::from_creation_error(e, requested))?;
 
     let ctx = unsafe { windowed_ctx.make_current().map_err(|(_, e)| e)? };
-
-    // init OpenGL
-    gl::load_with(|s| ctx.get_proc_address(s) as *const c_void);
-
-    ctx.window().set_visible(true);
-
-    let gl = GL33::new().map_err(GlutinError::GraphicsStateError)?;
-    let surface = GlutinSurface { ctx, gl };
+    let surface = Self::from_current_ctx(ctx, params)?;
 
     Ok((surface, event_loop))
   }
@@ -137,26 +792,197 @@ impl GlutinSurface {
     window_builder: WindowBuilder,
     samples: u16,
   ) -> Result<(Self, EventLoop<()>), GlutinError> {
-    let event_loop = EventLoop::new();
+    let event_loop = new_event_loop()?;
+    let surface = Self::new_gl33_with_event_loop(&event_loop, window_builder, samples)?;
 
-    let windowed_ctx = ContextBuilder::new()
-      .with_gl(GlRequest::Specific(Api::OpenGl, (3, 3)))
-      .with_gl_profile(GlProfile::Core)
-      .with_multisampling(samples)
-      .with_double_buffer(Some(true))
-      .build_windowed(window_builder, &event_loop)?;
+    Ok((surface, event_loop))
+  }
 
-    let ctx = unsafe { windowed_ctx.make_current().map_err(|(_, e)| e)? };
+  /// Create a new [`GlutinSurface`] on an existing event loop.
+  ///
+  /// This is the same as [`GlutinSurface::new_gl33`], but the event loop is borrowed instead of
+  /// being created by the surface. Use this when the event loop is owned by someone else, for
+  /// instance another library, or when it needs to be created in a specific way.
+  ///
+  /// # Platform-specific
+  ///
+  /// winit configures event loops with the extension traits of [`glutin::platform`], which
+  /// provide dedicated constructors:
+  ///
+  /// - **Windows:** `EventLoopExtWindows::new_any_thread` creates the event loop (and hence the
+  ///   surface) outside of the main thread.
+  /// - **Linux and the BSDs:** `EventLoopExtUnix::new_x11` and `EventLoopExtUnix::new_wayland`
+  ///   select the display server instead of letting winit pick one (Wayland first, then X11),
+  ///   and `EventLoopExtUnix::new_any_thread` creates the event loop outside of the main thread.
+  /// - **Android:** the event loop is created by `EventLoop::new`, from the activity set up by
+  ///   `ndk-glue`; there is nothing to configure.
+  ///
+  /// Other platforms only allow an event loop on the main thread, created with
+  /// [`EventLoop::new`].
+  pub fn new_gl33_with_event_loop<T>(
+    event_loop: &EventLoopWindowTarget<T>,
+    window_builder: WindowBuilder,
+    samples: u16,
+  ) -> Result<Self, GlutinError> {
+    GlutinSurfaceBuilder::new(window_builder)
+      .with_samples(samples)
+      .build(event_loop)
+  }
 
+  /// Finish creating a [`GlutinSurface`] once its windowed context is built and current.
+  fn from_current_ctx(
+    ctx: WindowedContext<PossiblyCurrent>,
+    params: CreationParams,
+  ) -> Result<Self, GlutinError> {
     // init OpenGL
     gl::load_with(|s| ctx.get_proc_address(s) as *const c_void);
 
     ctx.window().set_visible(true);
 
     let gl = GL33::new().map_err(GlutinError::GraphicsStateError)?;
-    let surface = GlutinSurface { ctx, gl };
+    let glsl_version = query_glsl_version(&gl)?;
+    let quirks = detect_quirks(&gl);
+    let decorated = params.window_builder.window.decorations;
 
-    Ok((surface, event_loop))
+    let context_flags = ContextFlags::query();
+    let extensions = query_extensions();
+    let limits = GlLimits::query();
+    let present_mode = Vsync::from_bool(params.ctx_builder.gl_attr.vsync).into();
+    let theme = window_theme(ctx.window());
+
+    let surface = GlutinSurface {
+      ctx,
+      gl,
+      glsl_version,
+      quirks,
+      context_flags,
+      extensions,
+      limits,
+      params,
+      render_scale: 1.,
+      render_target: None,
+      size_version: 0,
+      captured_size_version: 0,
+      monitor: None,
+      present_mode,
+      forced_swap_control: None,
+      swap_control: None,
+      default_clear: None,
+      clear_on_resize: false,
+      coalesce_resizes: false,
+      pending_resize: None,
+      draw_buffer: DrawBuffer::Back,
+      read_buffer: ReadBuffer::Back,
+      swap_timing: None,
+      presentation_clock: PresentationClock::new(),
+      present_latency: std::time::Duration::ZERO,
+      background_fps: None,
+      frame_limiter: FrameLimiter::default(),
+      frame_budget: None,
+      scissor_stack: Vec::new(),
+      drag_regions: Vec::new(),
+      cursor_position: None,
+      event_filter: None,
+      close_handler: None,
+      #[cfg(feature = "input")]
+      drop_target: DropTarget::new(),
+      #[cfg(feature = "input")]
+      touch_state: TouchState::new(),
+      decorated,
+      minimized: Cell::new(false),
+      focused: true,
+      theme,
+      scratch_framebuffers: HashMap::new(),
+      presenter: present::Presenter::default(),
+      #[cfg(feature = "clipboard")]
+      clipboard: RefCell::new(None),
+    };
+
+    Ok(surface)
+  }
+
+  /// Recreate the window and the OpenGL context of the surface, then rebuild resources.
+  ///
+  /// This is meant for total recovery, typically after a context loss (see
+  /// [`GlutinSurfaceBuilder::with_reset_notification`]): the window and its context are destroyed
+  /// and created again on `event_loop`, with the parameters the surface was originally created
+  /// with, except for context sharing, which is not kept. The luminance graphics state is then
+  /// reset for the new context, and `rebuild` is called to recreate your GPU resources.
+  ///
+  /// All the resources created with the previous context (tessellations, textures, framebuffers,
+  /// shader programs, etc.) must be dropped before calling this method: they would otherwise
+  /// refer to objects of the previous context, which might collide with objects of the new one.
+  /// The internal resources of the surface (scratch framebuffers, etc.) are dropped by this method.
+  pub fn recreate<T>(
+    &mut self,
+    event_loop: &EventLoopWindowTarget<T>,
+    rebuild: impl FnOnce(&mut Self),
+  ) -> Result<(), GlutinError> {
+    // drop the internal resources while their context is still current
+    if self.make_current().is_ok() {
+      self.scratch_framebuffers.clear();
+      self.render_target = None;
+      self.presenter = present::Presenter::default();
+    } else {
+      // the context is gone; prevent the resources from deleting objects of the new context
+      for fb in self.scratch_framebuffers.drain() {
+        std::mem::forget(fb);
+      }
+      std::mem::forget(self.render_target.take());
+      std::mem::forget(std::mem::take(&mut self.presenter));
+    }
+
+    let params = self.params.clone();
+    let requested = params.ctx_builder.pf_reqs.clone();
+    let windowed_ctx = params
+      .ctx_builder
+      .build_windowed(params.window_builder, event_loop)
+      .map_err(|e| GlutinError::from_creation_error(e, requested))?;
+    let ctx = unsafe { windowed_ctx.make_current().map_err(|(_, e)| e)? };
+
+    gl::load_with(|s| ctx.get_proc_address(s) as *const c_void);
+
+    // drop the previous window and context
+    self.ctx = ctx;
+    self.make_current()?;
+    self.ctx.window().set_visible(true);
+
+    unsafe { self.gl.state().borrow_mut().reset() }.map_err(GlutinError::GraphicsStateError)?;
+
+    self.glsl_version = query_glsl_version(&self.gl)?;
+    self.quirks = detect_quirks(&self.gl);
+    self.context_flags = ContextFlags::query();
+    self.extensions = query_extensions();
+    self.limits = GlLimits::query();
+    self.decorated = self.params.window_builder.window.decorations;
+    self.minimized.set(false);
+    self.focused = true;
+    self.theme = window_theme(self.ctx.window());
+    self.scissor_stack.clear();
+    self.cursor_position = None;
+    self.pending_resize = None;
+    // the default framebuffer of the new context has the default selection
+    self.draw_buffer = DrawBuffer::Back;
+    self.read_buffer = ReadBuffer::Back;
+    // the new window might have a different size
+    self.size_version += 1;
+
+    if self.monitor.is_some() {
+      self.monitor = Some(self.ctx.window().current_monitor());
+    }
+
+    self.presentation_clock.reset_feedback();
+    self.swap_control = None;
+
+    // the context was created with vsync, which is not relaxed
+    if self.present_mode == PresentMode::FifoRelaxed {
+      self.present_mode = PresentMode::Fifo;
+      self.set_present_mode(PresentMode::FifoRelaxed);
+    }
+
+    rebuild(self);
+
+    Ok(())
   }
 
   /// Get the underlying size (in physical pixels) of the surface.
@@ -168,13 +994,2618 @@ impl GlutinSurface {
     [size.width, size.height]
   }
 
-  /// Get access to the back buffer.
-  pub fn back_buffer(&mut self) -> Result<Framebuffer<GL33, Dim2, (), ()>, FramebufferError> {
-    Framebuffer::back_buffer(self, self.size())
+  /// Resize the surface, in physical pixels.
+  ///
+  /// The OpenGL surface is resized on the platforms requiring it. Scratch framebuffers are
+  /// recreated with the new size the next time they are asked for.
+  ///
+  /// A resize pending because resizes are coalesced (see [`GlutinSurface::set_coalesce_resizes`])
+  /// is discarded, as `size` supersedes it.
+  pub fn resize(&mut self, size: [u32; 2]) {
+    self.pending_resize = None;
+
+    let [width, height] = size;
+    self.ctx.resize(PhysicalSize::new(width, height));
+    self.size_version += 1;
+    self.clear_after_resize();
   }
 
-  /// Swap the back and front buffers.
-  pub fn swap_buffers(&mut self) {
-    let _ = self.ctx.swap_buffers();
+  /// Check whether resize events are coalesced.
+  ///
+  /// See [`GlutinSurface::set_coalesce_resizes`].
+  pub fn coalesce_resizes(&self) -> bool {
+    self.coalesce_resizes
+  }
+
+  /// Set whether resize events are coalesced.
+  ///
+  /// During a live resize, the platform can report many [`WindowEvent::Resized`] events per frame.
+  /// When resizes are coalesced, [`GlutinSurface::handle_window_event`] only records the latest
+  /// size, and the surface is resized once, when [`GlutinSurface::commit_pending_resize`] is
+  /// called; call it once per frame, before rendering. In the meantime, the surface keeps its
+  /// previous size, [`GlutinSurface::size_version`] is not incremented, and events still ask for
+  /// a redraw. Note that [`GlutinSurface::size`] reports the size of the window, which doesn’t
+  /// wait for the resize to be committed.
+  ///
+  /// Disabling coalescing commits the pending resize, if any. Disabled by default.
+  pub fn set_coalesce_resizes(&mut self, coalesce: bool) {
+    self.coalesce_resizes = coalesce;
+
+    if !coalesce {
+      self.commit_pending_resize();
+    }
+  }
+
+  /// Apply the latest size reported by a resize event, if resizes are coalesced and the surface
+  /// wasn’t resized since then.
+  ///
+  /// Returns whether the surface was resized. See [`GlutinSurface::set_coalesce_resizes`].
+  pub fn commit_pending_resize(&mut self) -> bool {
+    match self.pending_resize.take() {
+      Some(size) => {
+        self.resize(size);
+        true
+      }
+
+      None => false,
+    }
+  }
+
+  /// Check whether the back buffer is cleared when the surface is resized.
+  ///
+  /// See [`GlutinSurface::set_clear_on_resize`].
+  pub fn clear_on_resize(&self) -> bool {
+    self.clear_on_resize
+  }
+
+  /// Set whether the back buffer is cleared when the surface is resized.
+  ///
+  /// When enabled, [`GlutinSurface::resize`] and [`GlutinSurface::handle_dpi_change`] clear the
+  /// back buffer right away, with the default clear values (see
+  /// [`GlutinSurface::default_pipeline_state`]). On some platforms, the area uncovered by a resize
+  /// otherwise shows uninitialized content until the next frame, which flashes during live
+  /// resizing. Disabled by default.
+  pub fn set_clear_on_resize(&mut self, clear: bool) {
+    self.clear_on_resize = clear;
+  }
+
+  /// Clear the back buffer after a resize, if enabled.
+  fn clear_after_resize(&mut self) {
+    if !self.clear_on_resize || self.make_current().is_err() {
+      return;
+    }
+
+    // not going through back_buffer, which would hide the resize from is_resized_since_back_buffer
+    let size = self.size();
+
+    if let Ok(back_buffer) = Framebuffer::back_buffer(self, size) {
+      let state = self.default_pipeline_state();
+      let _ = self
+        .new_pipeline_gate()
+        .pipeline(&back_buffer, &state, |_, _| Ok::<_, PipelineError>(()))
+        .into_result();
+    }
+  }
+
+  /// Create an OpenGL context sharing its objects with the surface, for a worker thread.
+  ///
+  /// The returned [`WorkerContext`] is not current, and is meant to be sent to a worker thread, for
+  /// instance to stream textures in the background while the surface renders. See its
+  /// documentation for the threading rules, and for making the uploads visible to the surface.
+  ///
+  /// glutin creates contexts on an event loop, hence the `event_loop` argument. Creating the
+  /// context fails if the platform cannot share objects between the context of the window and an
+  /// offscreen one.
+  pub fn create_worker_context<T>(
+    &self,
+    event_loop: &EventLoopWindowTarget<T>,
+  ) -> Result<WorkerContext, GlutinError> {
+    WorkerContext::new(event_loop, &*self.ctx)
+  }
+
+  /// Get the size version of the surface.
+  ///
+  /// The size version is incremented every time the surface is resized, with
+  /// [`GlutinSurface::resize`] or [`GlutinSurface::handle_dpi_change`] (which
+  /// [`GlutinSurface::handle_window_event`] calls), when the window moves to another monitor (see
+  /// [`GlutinSurface::auto_reconfigure_on_monitor_change`]), and when the surface is recreated. Comparing it before and after rendering detects a resize happening in the middle
+  /// of a frame, which renders with a viewport and a back buffer of the old size: such a frame can
+  /// be skipped or restarted.
+  ///
+  /// Resizes the surface isn’t told about (when window events are not routed to it) don’t change
+  /// the size version.
+  pub fn size_version(&self) -> u64 {
+    self.size_version
+  }
+
+  /// Check whether the surface was resized since the back buffer was last obtained.
+  ///
+  /// [`GlutinSurface::back_buffer`], [`GlutinSurface::back_buffer_into`] and
+  /// [`GlutinSurface::frame`] capture the size version (see [`GlutinSurface::size_version`]) when
+  /// they get the back buffer, and so does [`GlutinSurface::swap_buffers`], as the next frame
+  /// starts. If this method returns `true`, the back buffer in use has an
+  /// outdated size and should be obtained again.
+  pub fn is_resized_since_back_buffer(&self) -> bool {
+    self.size_version != self.captured_size_version
+  }
+
+  /// Handle a window event affecting the surface.
+  ///
+  /// Pass this method the events of the window of the surface (events of other windows must not be
+  /// passed). It takes care of the events that every application must route to the surface:
+  ///
+  /// - [`WindowEvent::Resized`] resizes the surface (see [`GlutinSurface::resize`]), or records
+  ///   the size if resizes are coalesced (see [`GlutinSurface::set_coalesce_resizes`]), and asks
+  ///   for a redraw. A zero size, which some platforms report when the window is minimized, doesn’t
+  ///   resize the surface nor asks for a redraw, and marks the window as minimized (see
+  ///   [`GlutinSurface::is_minimized`]).
+  /// - [`WindowEvent::ScaleFactorChanged`] is handled with [`GlutinSurface::handle_dpi_change`]
+  ///   and asks for a redraw.
+  /// - With the `input` feature, drag and drop events are passed to the
+  ///   [`GlutinSurface::drop_target`], and touch events to the [`GlutinSurface::touch_state`].
+  /// - [`WindowEvent::Moved`] is handled if the surface tracks monitor changes (see
+  ///   [`GlutinSurface::auto_reconfigure_on_monitor_change`]).
+  /// - [`WindowEvent::Focused`] is tracked (see [`GlutinSurface::is_focused`]), and passed
+  ///   through.
+  /// - [`WindowEvent::ThemeChanged`] is tracked (see [`GlutinSurface::theme`]), reported in
+  ///   [`SurfaceEventOutcome::theme_changed`], and passed through.
+  /// - [`WindowEvent::CursorMoved`] and [`WindowEvent::CursorLeft`] are tracked, and passed
+  ///   through. A press of the left mouse button in a drag region (see
+  ///   [`GlutinSurface::set_drag_regions`]) starts dragging the window, and is handled.
+  /// - [`WindowEvent::CloseRequested`] is passed to the close handler, if any (see
+  ///   [`GlutinSurface::set_close_handler`]), and reported in
+  ///   [`SurfaceEventOutcome::close_requested`] unless vetoed. It is not acted upon: closing the
+  ///   window is up to the application.
+  ///
+  /// Other events are passed through: the returned outcome is empty and they are left to the
+  /// application.
+  ///
+  /// If an event filter is set (see [`GlutinSurface::set_event_filter`]), it is run first, and the
+  /// events it consumes are not handled.
+  pub fn handle_window_event(&mut self, event: &WindowEvent) -> SurfaceEventOutcome {
+    let mut outcome = SurfaceEventOutcome::default();
+
+    if let Some(ref mut filter) = self.event_filter {
+      if filter(event) {
+        return outcome;
+      }
+    }
+
+    match event {
+      WindowEvent::Resized(size) => {
+        outcome.handled = true;
+
+        if size.width != 0 && size.height != 0 {
+          if self.coalesce_resizes {
+            self.pending_resize = Some([size.width, size.height]);
+          } else {
+            self.resize([size.width, size.height]);
+          }
+
+          self.minimized.set(false);
+          outcome.redraw_needed = true;
+        } else {
+          self.minimized.set(true);
+        }
+      }
+
+      WindowEvent::ScaleFactorChanged { new_inner_size, .. } => {
+        self.handle_dpi_change(**new_inner_size);
+        outcome.handled = true;
+        outcome.redraw_needed = true;
+        self.reconfigure_on_monitor_change(&mut outcome);
+      }
+
+      WindowEvent::CloseRequested => {
+        outcome.close_requested = self
+          .close_handler
+          .as_mut()
+          .map_or(true, |handler| handler());
+        // a vetoed close request needs no further handling
+        outcome.handled = !outcome.close_requested;
+      }
+
+      WindowEvent::Moved(_) => {
+        self.reconfigure_on_monitor_change(&mut outcome);
+      }
+
+      WindowEvent::Focused(focused) => {
+        self.focused = *focused;
+      }
+
+      WindowEvent::ThemeChanged(theme) => {
+        self.theme = Some(*theme);
+        outcome.theme_changed = true;
+      }
+
+      WindowEvent::CursorMoved { position, .. } => {
+        self.cursor_position = Some(*position);
+      }
+
+      WindowEvent::CursorLeft { .. } => {
+        self.cursor_position = None;
+      }
+
+      WindowEvent::MouseInput {
+        state: ElementState::Pressed,
+        button: MouseButton::Left,
+        ..
+      } if self.is_in_drag_region() => {
+        outcome.handled = self.drag_window().is_ok();
+      }
+
+      #[cfg(feature = "input")]
+      _ if self.drop_target.handle_event(event) => {
+        outcome.handled = true;
+      }
+
+      #[cfg(feature = "input")]
+      _ if self.touch_state.handle_event(event) => {
+        outcome.handled = true;
+      }
+
+      _ => (),
+    }
+
+    outcome
+  }
+
+  /// Handle an event of the event loop, for applications rendering on demand.
+  ///
+  /// Pass this method all the events of the event loop. Events of the window of the surface are
+  /// handled with [`GlutinSurface::handle_window_event`], and [`SurfaceEventOutcome::render_now`]
+  /// tells when to render a frame:
+  ///
+  /// - On [`Event::RedrawRequested`] for the window of the surface, which the platform emits when
+  ///   the window must be repainted (e.g. after being exposed) and which is requested with
+  ///   [`GlutinSurface::request_redraw`].
+  /// - On the first event of the event loop ([`StartCause::Init`]), if no frame was presented
+  ///   yet. Some platforms (e.g. Wayland) only map the window once a first frame is presented,
+  ///   and never emit [`Event::RedrawRequested`] before that, so the first frame must be forced.
+  ///
+  /// Other events are passed through with an empty outcome.
+  ///
+  /// In a loop rendering only on demand, render, then call [`GlutinSurface::swap_buffers`], when
+  /// [`SurfaceEventOutcome::render_now`] is set, and call [`GlutinSurface::request_redraw`] when
+  /// the content changes, including when [`SurfaceEventOutcome::redraw_needed`] is set (most
+  /// platforms emit [`Event::RedrawRequested`] after a resize on their own, but not all of them).
+  /// Set the control flow of the event loop to [`ControlFlow::Wait`] to sleep in between. The
+  /// back buffer and the swap chain don’t need to be refreshed when no frame is rendered:
+  /// [`GlutinSurface::back_buffer`] always has the current size of the surface.
+  ///
+  /// [`ControlFlow::Wait`]: glutin::event_loop::ControlFlow::Wait
+  pub fn handle_event<T>(&mut self, event: &Event<T>) -> SurfaceEventOutcome {
+    let window_id = self.ctx.window().id();
+
+    match event {
+      Event::NewEvents(StartCause::Init) => SurfaceEventOutcome {
+        render_now: self.presentation_clock.last().is_none(),
+        ..SurfaceEventOutcome::default()
+      },
+
+      Event::WindowEvent {
+        window_id: id,
+        event,
+      } if *id == window_id => self.handle_window_event(event),
+
+      Event::RedrawRequested(id) if *id == window_id => SurfaceEventOutcome {
+        handled: true,
+        render_now: true,
+        ..SurfaceEventOutcome::default()
+      },
+
+      _ => SurfaceEventOutcome::default(),
+    }
+  }
+
+  /// Ask the platform to emit an [`Event::RedrawRequested`] for the window of the surface.
+  ///
+  /// Redraw requests are coalesced: the event is emitted once after the other pending events are
+  /// processed, however many times this is called. See [`GlutinSurface::handle_event`] to render
+  /// on demand.
+  pub fn request_redraw(&self) {
+    self.ctx.window().request_redraw();
+  }
+
+  /// Set a filter run on window events before the surface handles them.
+  ///
+  /// [`GlutinSurface::handle_window_event`] passes every event to `filter` before acting on it. If
+  /// `filter` returns `true`, the event is consumed: the surface doesn’t handle it and returns an
+  /// empty [`SurfaceEventOutcome`]. Otherwise, the event is handled as usual.
+  ///
+  /// This is useful to preprocess or remap events: consume the original event, and pass the
+  /// remapped one to [`GlutinSurface::handle_window_event`]. Synthetic events (e.g. resizes in UI
+  /// tests) can be injected the same way, and they go through the filter too.
+  ///
+  /// Setting a filter replaces the previous one.
+  pub fn set_event_filter(&mut self, filter: impl FnMut(&WindowEvent) -> bool + 'static) {
+    self.event_filter = Some(Box::new(filter));
+  }
+
+  /// Remove the event filter, if any; see [`GlutinSurface::set_event_filter`].
+  pub fn remove_event_filter(&mut self) {
+    self.event_filter = None;
+  }
+
+  /// Set a handler deciding whether the requests to close the window go through.
+  ///
+  /// When [`GlutinSurface::handle_window_event`] receives [`WindowEvent::CloseRequested`], it
+  /// calls `handler`. If `handler` returns `true`, the close request is reported in
+  /// [`SurfaceEventOutcome::close_requested`], and the application should close the window. If it
+  /// returns `false`, the close request is vetoed: it is not reported, and the window stays open.
+  /// This is typically used to ask the user to save their changes first; as `handler` must return
+  /// right away, veto the request and close the window later if the user confirms.
+  ///
+  /// Without a handler, every close request is reported. The surface never closes the window on
+  /// its own. Setting a handler replaces the previous one.
+  pub fn set_close_handler(&mut self, handler: impl FnMut() -> bool + 'static) {
+    self.close_handler = Some(Box::new(handler));
+  }
+
+  /// Remove the close handler, if any; see [`GlutinSurface::set_close_handler`].
+  pub fn remove_close_handler(&mut self) {
+    self.close_handler = None;
+  }
+
+  /// Automatically reconfigure the surface when the window moves to another monitor.
+  ///
+  /// When enabled, [`GlutinSurface::handle_window_event`] checks whether the current monitor of
+  /// the window changed when the window is moved or its scale factor changes. If it did, the
+  /// surface is reconfigured for the new monitor: it is resized to the current size of the window,
+  /// and the scratch framebuffers and the render target of [`GlutinSurface::frame`] are dropped so
+  /// that they are recreated for the new monitor. [`SurfaceEventOutcome::monitor_changed`] is then
+  /// set and a redraw is asked for.
+  ///
+  /// The OpenGL context is kept, and so are all the resources you created with it: the pixel
+  /// format of the window cannot change without recreating the window and its context, so it is
+  /// not switched to a format better suited to the new monitor.
+  ///
+  /// This is disabled by default.
+  pub fn auto_reconfigure_on_monitor_change(&mut self, enabled: bool) {
+    self.monitor = if enabled {
+      Some(self.ctx.window().current_monitor())
+    } else {
+      None
+    };
+  }
+
+  /// Reconfigure the surface if the window moved to another monitor and monitor changes are
+  /// tracked.
+  fn reconfigure_on_monitor_change(&mut self, outcome: &mut SurfaceEventOutcome) {
+    let monitor = match self.monitor {
+      Some(ref mut monitor) => monitor,
+      None => return,
+    };
+
+    let current = self.ctx.window().current_monitor();
+    if *monitor == current {
+      return;
+    }
+
+    *monitor = current;
+
+    self.resize(self.size());
+    self.clear_scratch_framebuffers();
+    self.render_target = None;
+
+    outcome.handled = true;
+    outcome.redraw_needed = true;
+    outcome.monitor_changed = true;
+  }
+
+  /// Handle a change of the scale factor of the window.
+  ///
+  /// Call this when receiving [`WindowEvent::ScaleFactorChanged`], passing the new inner size of
+  /// the window, in physical pixels; the scale factor itself is not needed. The surface is resized
+  /// to that size and the scratch framebuffers, which are sized to the old resolution, are
+  /// dropped.
+  ///
+  /// The new physical size of the surface is returned, so that you can rebuild your own render
+  /// targets.
+  ///
+  /// [`WindowEvent::ScaleFactorChanged`]: glutin::event::WindowEvent::ScaleFactorChanged
+  pub fn handle_dpi_change(&mut self, new_inner_size: PhysicalSize<u32>) -> [u32; 2] {
+    self.resize([new_inner_size.width, new_inner_size.height]);
+    self.clear_scratch_framebuffers();
+
+    [new_inner_size.width, new_inner_size.height]
+  }
+
+  /// Block until the window is exposed for the first time, or until `timeout` elapses.
+  ///
+  /// Some compositors only map a window some time after it is made visible, and discard what is
+  /// presented before that, so the first frame might never be displayed. This method pumps the
+  /// events of `event_loop` until the window receives its first [`Event::RedrawRequested`], which
+  /// happens once the window can actually be drawn to. Call it right after creating the surface,
+  /// before rendering the first frame.
+  ///
+  /// The window events of the surface pumped while waiting (resizes, scale factor changes, close
+  /// requests, etc.) are passed to [`GlutinSurface::handle_window_event`], and their merged
+  /// outcome is returned along with whether the window was exposed: `false` means that `timeout`
+  /// elapsed first, or that closing the window was requested, which stops waiting. Rendering can
+  /// proceed in both cases, unless the window is to be closed. The other events are discarded.
+  ///
+  /// This is available on the platforms where the event loop can be run temporarily (Windows,
+  /// macOS, Linux, the BSDs and Android).
+  ///
+  /// [`Event::RedrawRequested`]: glutin::event::Event::RedrawRequested
+  #[cfg(any(
+    target_os = "windows",
+    target_os = "macos",
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd",
+    target_os = "android",
+  ))]
+  pub fn wait_for_first_expose<T>(
+    &mut self,
+    event_loop: &mut EventLoop<T>,
+    timeout: std::time::Duration,
+  ) -> (bool, SurfaceEventOutcome) {
+    use glutin::event_loop::ControlFlow;
+    use glutin::platform::run_return::EventLoopExtRunReturn;
+    use std::time::Instant;
+
+    let window_id = self.ctx.window().id();
+    let deadline = Instant::now() + timeout;
+    let mut exposed = false;
+    let mut outcome = SurfaceEventOutcome::default();
+
+    self.ctx.window().request_redraw();
+
+    event_loop.run_return(|event, _, control_flow| {
+      match event {
+        Event::RedrawRequested(id) if id == window_id => exposed = true,
+
+        Event::WindowEvent {
+          window_id: id,
+          ref event,
+        } if id == window_id => outcome.merge(self.handle_window_event(event)),
+
+        _ => (),
+      }
+
+      *control_flow = if exposed || outcome.close_requested || Instant::now() >= deadline {
+        ControlFlow::Exit
+      } else {
+        ControlFlow::WaitUntil(deadline)
+      };
+    });
+
+    (exposed, outcome)
+  }
+
+  /// Set whether the window has decorations (title bar, borders, etc.).
+  ///
+  /// On some platforms, toggling decorations changes the size of the client area of the window.
+  /// The window manager applies it asynchronously on some of them (e.g. X11 and Wayland), so the
+  /// surface is not resized here: the window reports its new size with a
+  /// [`WindowEvent::Resized`], which [`GlutinSurface::handle_window_event`] handles by resizing
+  /// the surface. Route the window events to the surface for the back buffer to keep matching the
+  /// window.
+  pub fn set_decorations(&mut self, decorations: bool) {
+    self.ctx.window().set_decorations(decorations);
+    self.decorated = decorations;
+  }
+
+  /// Set the regions of the window that drag it when clicked, for custom-drawn title bars.
+  ///
+  /// Regions are expressed as `[x, y, width, height]` in physical pixels, with the origin in the
+  /// lower-left corner of the surface, like viewports (see [`GlutinSurface::set_viewport`]), so
+  /// that they can match what is rendered. When [`GlutinSurface::handle_window_event`] receives a
+  /// left button press while the cursor is in one of the regions, the window starts being dragged
+  /// by the window manager (see [`GlutinSurface::drag_window`]) and the event is reported as
+  /// handled. The cursor position is tracked from the events passed to it as well.
+  ///
+  /// Regions are kept as-is when the surface is resized; update them along with the layout of the
+  /// title bar. Setting regions replaces the previous ones, and an empty slice (the default)
+  /// disables dragging.
+  ///
+  /// # Platform-specific
+  ///
+  /// winit only supports moving windows: resizing a borderless window by dragging its edges, and
+  /// hit-testing (e.g. to show the snap layouts of Windows 11 when hovering a custom maximize
+  /// button), are not available.
+  ///
+  /// - **Windows, macOS, X11, Wayland:** dragging is supported.
+  /// - **iOS, Android:** dragging is not supported, and presses in the regions are not handled.
+  pub fn set_drag_regions(&mut self, regions: &[[u32; 4]]) {
+    self.drag_regions = regions.to_vec();
+  }
+
+  /// Start dragging the window with the left mouse button, until it is released.
+  ///
+  /// This must be called while the left mouse button is pressed, typically when handling its
+  /// press; otherwise, it has no effect or fails depending on the platform. Prefer
+  /// [`GlutinSurface::set_drag_regions`] for custom-drawn title bars.
+  pub fn drag_window(&self) -> Result<(), ExternalError> {
+    self.ctx.window().drag_window()
+  }
+
+  /// Check whether the cursor is in a drag region; see [`GlutinSurface::set_drag_regions`].
+  fn is_in_drag_region(&self) -> bool {
+    let position = match self.cursor_position {
+      Some(position) => position,
+      None => return false,
+    };
+
+    // drag regions have their origin in the lower-left corner, the cursor in the upper-left one
+    let x = position.x;
+    let y = self.size()[1] as f64 - position.y;
+
+    self.drag_regions.iter().any(|&[rx, ry, width, height]| {
+      let (rx, ry) = (rx as f64, ry as f64);
+      x >= rx && x < rx + width as f64 && y >= ry && y < ry + height as f64
+    })
+  }
+
+  /// Check whether the window has decorations.
+  ///
+  /// This reflects the value the window was created with, or the last value passed to
+  /// [`GlutinSurface::set_decorations`].
+  pub fn is_decorated(&self) -> bool {
+    self.decorated
+  }
+
+  /// Minimize or restore the window.
+  ///
+  /// This is a request to the window manager, which carries it out asynchronously (or not at all:
+  /// some platforms, such as Wayland, don’t allow restoring a minimized window). The surface has no
+  /// area while the window is minimized on some platforms; don’t render until it is restored.
+  pub fn set_minimized(&self, minimized: bool) {
+    self.ctx.window().set_minimized(minimized);
+    self.minimized.set(minimized);
+  }
+
+  /// Check whether the window is minimized.
+  ///
+  /// winit cannot query this state, so this reflects the last value passed to
+  /// [`GlutinSurface::set_minimized`], updated by [`GlutinSurface::handle_window_event`] on the
+  /// platforms reporting a zero size when the window is minimized. The window manager might
+  /// minimize or restore the window without the surface knowing about it elsewhere.
+  pub fn is_minimized(&self) -> bool {
+    self.minimized.get()
+  }
+
+  /// Maximize or unmaximize the window.
+  ///
+  /// This is a request to the window manager, which carries it out asynchronously: the size of the
+  /// window is not updated when this method returns. Once it is, a [`WindowEvent::Resized`] event
+  /// is emitted, and passing it to [`GlutinSurface::handle_window_event`] resizes the surface. This
+  /// is typically used to restore a saved window state on startup.
+  pub fn set_maximized(&self, maximized: bool) {
+    self.ctx.window().set_maximized(maximized);
+  }
+
+  /// Check whether the window is maximized.
+  ///
+  /// This is queried from the window manager, so it might not reflect a request made with
+  /// [`GlutinSurface::set_maximized`] yet.
+  pub fn is_maximized(&self) -> bool {
+    self.ctx.window().is_maximized()
+  }
+
+  /// Get the raw handle of the window, to interoperate with other graphics libraries.
+  ///
+  /// With the version of [raw-window-handle](https://crates.io/crates/raw-window-handle) used by
+  /// winit, there is no separate display handle: the connection to the display server is part of
+  /// the window handle on the platforms having one (Xlib, XCB and Wayland).
+  ///
+  /// The handle is valid as long as the surface is alive, and until the window is recreated with
+  /// [`GlutinSurface::recreate`]: get it again afterwards.
+  pub fn raw_window_handle(&self) -> RawWindowHandle {
+    self.ctx.window().raw_window_handle()
+  }
+
+  /// Get the geometry of the window, to restore it later with [`GlutinSurface::apply_geometry`].
+  ///
+  /// When the window is maximized, the size is the maximized one: winit doesn’t report the size
+  /// the window is restored to when unmaximized.
+  pub fn geometry(&self) -> WindowGeometry {
+    let window = self.ctx.window();
+
+    WindowGeometry {
+      position: window.outer_position().ok().map(|p| [p.x, p.y]),
+      size: self.size(),
+      maximized: window.is_maximized(),
+    }
+  }
+
+  /// Restore the geometry of the window.
+  ///
+  /// The window is clamped to the monitors currently available, so that a window saved on a
+  /// monitor that has since been disconnected (or whose resolution changed) shows up on screen: if
+  /// the window is not entirely covered by the monitors, it is moved (and shrunk if needed) into
+  /// the monitor it overlaps the most, or into the primary monitor if it overlaps none. A window
+  /// spanning several monitors is left untouched. Decorations are not accounted for, so the
+  /// window might still slightly overflow its monitor.
+  ///
+  /// Like [`GlutinSurface::set_maximized`], this is a request to the window manager, which
+  /// carries it out asynchronously; the surface is resized once the [`WindowEvent::Resized`]
+  /// event is passed to [`GlutinSurface::handle_window_event`]. The position is ignored on the
+  /// platforms that don’t allow to set it (Wayland).
+  pub fn apply_geometry(&mut self, geometry: &WindowGeometry) {
+    let window = self.ctx.window();
+    let primary = window.primary_monitor();
+    let monitors: Vec<_> = primary
+      .iter()
+      .cloned()
+      .chain(
+        window
+          .available_monitors()
+          .filter(|m| Some(m) != primary.as_ref()),
+      )
+      .map(|m| {
+        let (position, size) = (m.position(), m.size());
+        ([position.x, position.y], [size.width, size.height])
+      })
+      .collect();
+
+    let position = geometry
+      .position
+      .unwrap_or_else(|| window.outer_position().map_or([0, 0], |p| [p.x, p.y]));
+    let ([x, y], [width, height]) = geometry::clamp_to_monitors(position, geometry.size, &monitors);
+
+    window.set_maximized(false);
+    window.set_inner_size(PhysicalSize::new(width, height));
+
+    if geometry.position.is_some() {
+      window.set_outer_position(PhysicalPosition::new(x, y));
+    }
+
+    window.set_maximized(geometry.maximized);
+  }
+
+  /// Get the aspect ratio (width / height) of the surface.
+  ///
+  /// If the surface has no area (for instance when the window is minimized), `0.` is returned.
+  pub fn aspect_ratio(&self) -> f32 {
+    let [width, height] = self.size();
+
+    if width == 0 || height == 0 {
+      0.
+    } else {
+      width as f32 / height as f32
+    }
+  }
+
+  /// Compute the largest viewport preserving `target_aspect` (width / height) within the surface.
+  ///
+  /// The viewport is returned as `[x, y, width, height]`, in physical pixels, and is centered in
+  /// the surface: bars are left on the left and right sides if the surface is wider than
+  /// `target_aspect` (pillarboxing), and on the top and bottom sides otherwise (letterboxing).
+  ///
+  /// If the surface has no area or if `target_aspect` is not strictly positive, the whole surface
+  /// is returned.
+  pub fn letterbox_viewport(&self, target_aspect: f32) -> [u32; 4] {
+    let [width, height] = self.size();
+
+    if width == 0 || height == 0 || target_aspect.is_nan() || target_aspect <= 0. {
+      return [0, 0, width, height];
+    }
+
+    if (width as f32 / height as f32) > target_aspect {
+      let w = ((height as f32 * target_aspect).round() as u32).min(width);
+      [(width - w) / 2, 0, w, height]
+    } else {
+      let h = ((width as f32 / target_aspect).round() as u32).min(height);
+      [0, (height - h) / 2, width, h]
+    }
+  }
+
+  /// Compute the viewports of a split-screen layout, from the current size of the surface.
+  ///
+  /// Viewports are expressed as `[x, y, width, height]`, in physical pixels, with the origin in
+  /// the lower-left corner of the surface, like [`GlutinSurface::set_viewport`]. They are
+  /// returned in reading order (see [`SplitLayout`]), and tile the surface exactly.
+  pub fn viewports(&self, layout: SplitLayout) -> Vec<[u32; 4]> {
+    viewport::split(self.size(), layout)
+  }
+
+  /// Run `f` for each viewport of a split-screen layout, for instance to render the view of each
+  /// player in local multiplayer games.
+  ///
+  /// `f` is passed the surface, the index of the viewport (see [`GlutinSurface::viewports`] for
+  /// the order) and a copy of `state` whose viewport and clear scissor are set to the viewport:
+  /// pipelines run with it only render to, and clear, their viewport. The results of `f` are
+  /// returned in the same order.
+  pub fn render_viewports<R>(
+    &mut self,
+    layout: SplitLayout,
+    state: &PipelineState,
+    mut f: impl FnMut(&mut Self, usize, &PipelineState) -> R,
+  ) -> Vec<R> {
+    self
+      .viewports(layout)
+      .into_iter()
+      .enumerate()
+      .map(|(i, [x, y, width, height])| {
+        let state = state
+          .clone()
+          .set_viewport(Viewport::Specific {
+            x,
+            y,
+            width,
+            height,
+          })
+          .set_scissor(ScissorRegion {
+            x,
+            y,
+            width,
+            height,
+          });
+
+        f(self, i, &state)
+      })
+      .collect()
+  }
+
+  /// Get the drag and drop target of the window.
+  ///
+  /// Pass it the window events with [`DropTarget::handle_event`] in order to collect the files
+  /// dropped on the window.
+  #[cfg(feature = "input")]
+  pub fn drop_target(&mut self) -> &mut DropTarget {
+    &mut self.drop_target
+  }
+
+  /// Get the touch points in contact with the window.
+  ///
+  /// [`GlutinSurface::handle_window_event`] passes it the touch events of the window. Touch events
+  /// are only reported by platforms with touch screens (Windows, Wayland, X11 with XInput 2.2,
+  /// iOS and Android).
+  #[cfg(feature = "input")]
+  pub fn touch_state(&self) -> &TouchState {
+    &self.touch_state
+  }
+
+  /// Get the text held by the system clipboard.
+  ///
+  /// `None` is returned if the clipboard doesn’t hold any text or if it cannot be accessed.
+  #[cfg(feature = "clipboard")]
+  pub fn clipboard_text(&self) -> Option<String> {
+    self
+      .with_clipboard(|clipboard| clipboard.get_text().ok())
+      .flatten()
+  }
+
+  /// Put some text into the system clipboard.
+  ///
+  /// This is best-effort: if the clipboard cannot be accessed, nothing happens.
+  #[cfg(feature = "clipboard")]
+  pub fn set_clipboard_text(&self, text: &str) {
+    self.with_clipboard(|clipboard| {
+      let _ = clipboard.set_text(text);
+    });
+  }
+
+  /// Run a closure with the system clipboard, connecting to it first if needed.
+  ///
+  /// The connection is kept alive with the surface, as some platforms (X11, Wayland) drop the
+  /// content we put in the clipboard when the connection is closed.
+  #[cfg(feature = "clipboard")]
+  fn with_clipboard<R>(&self, f: impl FnOnce(&mut arboard::Clipboard) -> R) -> Option<R> {
+    let mut clipboard = self.clipboard.borrow_mut();
+
+    if clipboard.is_none() {
+      *clipboard = arboard::Clipboard::new().ok();
+    }
+
+    clipboard.as_mut().map(f)
+  }
+
+  /// Get access to the back buffer.
+  pub fn back_buffer(&mut self) -> Result<Framebuffer<GL33, Dim2, (), ()>, FramebufferError> {
+    self.select_draw_buffer(DrawBuffer::Back);
+    self.captured_size_version = self.size_version;
+    Framebuffer::back_buffer(self, self.size())
+  }
+
+  /// Check whether the default framebuffer is stereo, i.e. has left and right back buffers.
+  ///
+  /// See [`GlutinSurfaceBuilder::with_stereo`].
+  pub fn is_stereo(&self) -> bool {
+    self.ctx.get_pixel_format().stereoscopy
+  }
+
+  /// Get access to the back buffer of an eye of a stereo default framebuffer.
+  ///
+  /// Rendering to the returned back buffer only affects the back buffer of `eye`, until another
+  /// back buffer is asked for: [`GlutinSurface::back_buffer`], [`GlutinSurface::back_buffer_into`]
+  /// and [`GlutinSurface::frame`] draw to both back buffers again. Render each eye in turn, then
+  /// swap the buffers as usual.
+  ///
+  /// If the default framebuffer is not stereo (see [`GlutinSurface::is_stereo`]), the left eye is
+  /// the regular back buffer, and asking for the right eye fails with
+  /// [`FramebufferError::UnsupportedAttachment`].
+  pub fn back_buffer_stereo(
+    &mut self,
+    eye: StereoEye,
+  ) -> Result<Framebuffer<GL33, Dim2, (), ()>, FramebufferError> {
+    if eye == StereoEye::Right && !self.is_stereo() {
+      return Err(FramebufferError::unsupported_attachment());
+    }
+
+    self.select_draw_buffer(match eye {
+      StereoEye::Left => DrawBuffer::BackLeft,
+      StereoEye::Right => DrawBuffer::BackRight,
+    });
+    self.captured_size_version = self.size_version;
+    Framebuffer::back_buffer(self, self.size())
+  }
+
+  /// Get the color buffers of the default framebuffer selected for drawing.
+  ///
+  /// See [`GlutinSurface::set_draw_buffer`].
+  pub fn draw_buffer(&self) -> DrawBuffer {
+    self.draw_buffer
+  }
+
+  /// Select the color buffers of the default framebuffer to draw to (`glDrawBuffer`).
+  ///
+  /// The default is [`DrawBuffer::Back`]. Rendering to the back buffer, and blitting to it, then
+  /// affects the selected color buffers, until another back buffer is asked for:
+  /// [`GlutinSurface::back_buffer`], [`GlutinSurface::back_buffer_into`] and
+  /// [`GlutinSurface::frame`] select [`DrawBuffer::Back`] again, and
+  /// [`GlutinSurface::back_buffer_stereo`] selects the back buffer of its eye. Select the draw
+  /// buffer after getting the back buffer.
+  ///
+  /// Drawing to [`DrawBuffer::Front`] shows up without swapping the buffers, possibly with
+  /// tearing. [`DrawBuffer::BackRight`] fails with [`FramebufferError::UnsupportedAttachment`] if
+  /// the default framebuffer is not stereo (see [`GlutinSurface::is_stereo`]).
+  pub fn set_draw_buffer(&mut self, buffer: DrawBuffer) -> Result<(), FramebufferError> {
+    if buffer == DrawBuffer::BackRight && !self.is_stereo() {
+      return Err(FramebufferError::unsupported_attachment());
+    }
+
+    self.select_draw_buffer(buffer);
+    Ok(())
+  }
+
+  /// Select the color buffers of the default framebuffer to draw to.
+  fn select_draw_buffer(&mut self, buffer: DrawBuffer) {
+    if self.draw_buffer == buffer {
+      return;
+    }
+
+    // the draw buffers are a state of the framebuffer, so the default framebuffer must be bound
+    unsafe {
+      gl::BindFramebuffer(gl::DRAW_FRAMEBUFFER, 0);
+      gl::DrawBuffer(buffer.to_gl());
+      self.gl.state().borrow_mut().invalidate_framebuffer();
+    }
+
+    self.draw_buffer = buffer;
+  }
+
+  /// Get the color buffer of the default framebuffer selected for reading.
+  ///
+  /// See [`GlutinSurface::set_read_buffer`].
+  pub fn read_buffer(&self) -> ReadBuffer {
+    self.read_buffer
+  }
+
+  /// Select the color buffer of the default framebuffer to read from (`glReadBuffer`).
+  ///
+  /// The default is [`ReadBuffer::Back`]. The selection applies to every read of the default
+  /// framebuffer, until another color buffer is selected: [`GlutinSurface::read_back_buffer`] and
+  /// its variants, as well as blits from the back buffer. Select [`ReadBuffer::Front`] to read what
+  /// is displayed after swapping the buffers, keeping in mind that the content of the front buffer
+  /// is undefined where the window is covered by other windows on some platforms.
+  ///
+  /// [`ReadBuffer::BackRight`] fails with [`FramebufferError::UnsupportedAttachment`] if the
+  /// default framebuffer is not stereo (see [`GlutinSurface::is_stereo`]).
+  pub fn set_read_buffer(&mut self, buffer: ReadBuffer) -> Result<(), FramebufferError> {
+    if buffer == ReadBuffer::BackRight && !self.is_stereo() {
+      return Err(FramebufferError::unsupported_attachment());
+    }
+
+    if self.read_buffer != buffer {
+      // the read buffer is a state of the framebuffer, so the default framebuffer must be bound
+      unsafe {
+        gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+        gl::ReadBuffer(buffer.to_gl());
+        self.gl.state().borrow_mut().invalidate_framebuffer();
+      }
+
+      self.read_buffer = buffer;
+    }
+
+    Ok(())
+  }
+
+  /// Get access to the back buffer, reusing a previously obtained one if still valid.
+  ///
+  /// `out` is a back buffer cache owned by the caller. If it is empty or if its size differs from
+  /// the current size of the surface, a new back buffer is created and stored in it; otherwise,
+  /// the cached back buffer is kept as-is. Either way, the back buffer stored in `out` is
+  /// returned.
+  ///
+  /// A back buffer only becomes invalid when the surface is resized, so calling this method every
+  /// frame with the same `out` only creates a new back buffer after a resize. If creating it
+  /// fails, `out` is left empty.
+  pub fn back_buffer_into<'a>(
+    &mut self,
+    out: &'a mut Option<Framebuffer<GL33, Dim2, (), ()>>,
+  ) -> Result<&'a mut Framebuffer<GL33, Dim2, (), ()>, FramebufferError> {
+    self.select_draw_buffer(DrawBuffer::Back);
+    self.captured_size_version = self.size_version;
+    let size = self.size();
+
+    match out {
+      Some(back_buffer) if back_buffer.size() == size => (),
+
+      _ => {
+        *out = None;
+        *out = Some(Framebuffer::back_buffer(self, size)?);
+      }
+    }
+
+    Ok(out.as_mut().expect("back buffer"))
+  }
+
+  /// Set the backdrop effect drawn by the system behind the window, returning whether it was
+  /// applied.
+  ///
+  /// The surface doesn’t support transparency: the OpenGL content covers the client area of the
+  /// window, so the backdrop only shows in the areas drawn by the system, such as the title bar.
+  ///
+  /// # Platform-specific
+  ///
+  /// - **Windows:** requires Windows 11 22H2 or later, where the backdrop is set with
+  ///   `DWMWA_SYSTEMBACKDROP_TYPE`; older versions don’t apply it.
+  /// - **macOS, X11, Wayland:** unsupported; only [`Backdrop::None`] is reported as applied.
+  pub fn set_backdrop(&self, backdrop: Backdrop) -> bool {
+    backdrop::set_backdrop(self.ctx.window(), backdrop)
+  }
+
+  /// Show the progress of a long operation (e.g. an export) in the taskbar entry of the window.
+  ///
+  /// `progress` goes from `0` (started) to `1` (done), and is clamped to that range; `None` hides
+  /// the progress.
+  ///
+  /// # Platform-specific
+  ///
+  /// - **Windows:** the progress is shown in the taskbar button of the window, with
+  ///   `ITaskbarList3`. This requires COM to be initialized on the thread of the window, which
+  ///   winit does unless drag and drop is disabled; otherwise, this has no effect.
+  /// - **macOS, X11, Wayland:** unsupported; this has no effect. (The Unity launcher API of some
+  ///   Linux desktops goes through D-Bus and requires a `.desktop` file, which is out of the scope
+  ///   of the surface.)
+  pub fn set_taskbar_progress(&self, progress: Option<f32>) {
+    taskbar::set_progress(self.ctx.window(), progress);
+  }
+
+  /// Set the level of the window, relative to other windows.
+  ///
+  /// # Platform-specific
+  ///
+  /// Window levels are not supported on iOS, Android, the Web and Wayland, where calling this
+  /// method has no effect.
+  pub fn set_window_level(&self, level: WindowLevel) {
+    self
+      .ctx
+      .window()
+      .set_always_on_top(level == WindowLevel::AlwaysOnTop);
+  }
+
+  /// Get a scratch framebuffer with the same size as the surface.
+  ///
+  /// Scratch framebuffers are transient render targets, typically used by post-processing passes.
+  /// They are lazily created the first time they are asked for and then cached, one per format
+  /// (i.e. per color and depth / stencil slots). If the size of the surface changed since the
+  /// last call, the scratch framebuffer is recreated with the new size, so its content is lost.
+  ///
+  /// Each format allocates GPU memory for a whole window-sized framebuffer;
+  /// [`GlutinSurface::clear_scratch_framebuffers`] releases them.
+  pub fn scratch_framebuffer<CS, DS>(
+    &mut self,
+  ) -> Result<&mut Framebuffer<GL33, Dim2, CS, DS>, FramebufferError>
+  where
+    CS: ColorSlot<GL33, Dim2> + 'static,
+    DS: DepthStencilSlot<GL33, Dim2> + 'static,
+  {
+    let size = self.size();
+    let key = TypeId::of::<Framebuffer<GL33, Dim2, CS, DS>>();
+    let outdated = self
+      .scratch_framebuffers
+      .get(&key)
+      .and_then(|fb| fb.downcast_ref::<Framebuffer<GL33, Dim2, CS, DS>>())
+      .map_or(true, |fb| fb.size() != size);
+
+    if outdated {
+      // release the previous framebuffer before allocating its replacement
+      self.scratch_framebuffers.remove(&key);
+
+      let fb = Framebuffer::<GL33, Dim2, CS, DS>::new(self, size, 0, Sampler::default())?;
+      self.scratch_framebuffers.insert(key, Box::new(fb));
+    }
+
+    Ok(
+      self
+        .scratch_framebuffers
+        .get_mut(&key)
+        .and_then(|fb| fb.downcast_mut())
+        .expect("scratch framebuffer"),
+    )
+  }
+
+  /// Render into a texture, then get back to the default framebuffer.
+  ///
+  /// In luminance, textures are rendered into by attaching them to a framebuffer, which owns them.
+  /// This helper uses the scratch framebuffer with color slot `CS` and depth / stencil slot `DS`
+  /// (see [`GlutinSurface::scratch_framebuffer`]) as render target: `f` is passed the surface, to
+  /// create pipeline gates, and the framebuffer to render into. Once `f` returns, the default
+  /// framebuffer is bound again and the rendered texture can be used while rendering to the back
+  /// buffer, by getting it with [`Framebuffer::color_slot`] on the scratch framebuffer.
+  ///
+  /// The depth attachment is driven by `DS`: use `()` if the pass doesn’t need depth testing, or a
+  /// depth format such as [`Depth32F`] to get a depth buffer, which you can later sample with
+  /// [`Framebuffer::depth_stencil_slot`]. The depth buffer is not shared with the default
+  /// framebuffer.
+  ///
+  /// [`Depth32F`]: luminance::pixel::Depth32F
+  pub fn render_to_texture<CS, DS, R>(
+    &mut self,
+    f: impl FnOnce(&mut Self, &mut Framebuffer<GL33, Dim2, CS, DS>) -> R,
+  ) -> Result<R, FramebufferError>
+  where
+    CS: ColorSlot<GL33, Dim2> + 'static,
+    DS: DepthStencilSlot<GL33, Dim2> + 'static,
+  {
+    self.scratch_framebuffer::<CS, DS>()?;
+
+    // move the framebuffer out of the pool while rendering so that the surface can be borrowed
+    let key = TypeId::of::<Framebuffer<GL33, Dim2, CS, DS>>();
+    let mut fb = self
+      .scratch_framebuffers
+      .remove(&key)
+      .expect("scratch framebuffer");
+    let r = f(self, fb.downcast_mut().expect("scratch framebuffer"));
+    self.scratch_framebuffers.insert(key, fb);
+
+    unsafe {
+      gl::BindFramebuffer(gl::FRAMEBUFFER, 0);
+      self.gl.state().borrow_mut().invalidate_framebuffer();
+    }
+
+    Ok(r)
+  }
+
+  /// Set the scale of the internal render target of [`GlutinSurface::frame`].
+  ///
+  /// The render target is sized to `scale` times the size of the surface: a scale lower than `1.`
+  /// renders at a lower resolution and upscales to the back buffer, which is typically used to
+  /// trade quality for performance (dynamic resolution); a scale greater than `1.` supersamples.
+  /// Scales that are not strictly positive are ignored. The default scale is `1.`.
+  pub fn set_render_scale(&mut self, scale: f32) {
+    if scale.is_nan() || scale <= 0. || scale == self.render_scale {
+      return;
+    }
+
+    self.render_scale = scale;
+    self.render_target = None;
+  }
+
+  /// Draw a texture to the whole back buffer, then present it.
+  ///
+  /// This runs a built-in pass drawing a triangle covering the back buffer and sampling `texture`
+  /// with normalized coordinates, so the texture is stretched to the size of the surface. It is
+  /// sampled according to its own [`Sampler`] (typically linear filtering with clamped edges), and
+  /// its texels are written as-is: color textures with up to four channels are expected, the
+  /// missing channels reading as `0` (and alpha as `1`). Integral textures are converted to floats,
+  /// which the back buffer clamps to `[0, 1]`. Neither blending, nor the depth test, nor sRGB
+  /// conversion are applied. The buffers are then swapped (see [`GlutinSurface::swap_buffers`]),
+  /// and the outcome of the swap is returned.
+  ///
+  /// This is meant for offscreen-then-present rendering, such as post-processing. The shader
+  /// program is created on the first call for each sampler type, and cached.
+  #[cfg(feature = "present")]
+  pub fn present_texture<P>(
+    &mut self,
+    texture: &mut Texture<GL33, Dim2, P>,
+  ) -> Result<SwapResult, GlutinError>
+  where
+    P: Pixel,
+    P::SamplerType: 'static,
+  {
+    self.select_draw_buffer(DrawBuffer::Back);
+    self.captured_size_version = self.size_version;
+    let size = self.size();
+
+    self.draw_to_back_buffer(texture, size)?;
+    self.swap_buffers()
+  }
+
+  /// Draw a texture to the back buffer, with a viewport of size `size`, without swapping.
+  ///
+  /// This is the presentation pass of [`GlutinSurface::frame`] and
+  /// [`GlutinSurface::present_texture`].
+  fn draw_to_back_buffer<P>(
+    &mut self,
+    texture: &mut Texture<GL33, Dim2, P>,
+    size: [u32; 2],
+  ) -> Result<(), GlutinError>
+  where
+    P: Pixel,
+    P::SamplerType: 'static,
+  {
+    let mut program = match self.presenter.take_program::<P::SamplerType>() {
+      Some(program) => program,
+
+      None => self
+        .new_shader_program::<(), (), present::PresentInterface<P::SamplerType>>()
+        .from_strings(
+          present::VS,
+          None,
+          None,
+          &present::fragment_shader::<P::SamplerType>(),
+        )?
+        .ignore_warnings(),
+    };
+
+    let tess = match self.presenter.tess.take() {
+      Some(tess) => tess,
+      None => self
+        .new_tess()
+        .set_mode(Mode::Triangle)
+        .set_render_vertex_nb(3)
+        .build()?,
+    };
+
+    let back_buffer = Framebuffer::back_buffer(self, size)?;
+    let state = PipelineState::default()
+      .set_clear_color(None)
+      .set_clear_depth(None)
+      .set_clear_stencil(None);
+    let render: Result<(), PipelineError> = self
+      .new_pipeline_gate()
+      .pipeline(&back_buffer, &state, |pipeline, mut shd_gate| {
+        let texture = pipeline.bind_texture(texture)?;
+
+        shd_gate.shade(&mut program, |mut iface, uni, mut rdr_gate| {
+          iface.set(&uni.tex, texture.binding());
+
+          let rdr_st = RenderState::default().set_depth_test(None);
+          rdr_gate.render(&rdr_st, |mut tess_gate| tess_gate.render(&tess))
+        })
+      })
+      .into_result();
+
+    self.presenter.put_program(program);
+    self.presenter.tess = Some(tess);
+
+    render?;
+    Ok(())
+  }
+
+  /// Set the default clear color and depth of the surface.
+  ///
+  /// In luminance, framebuffers are cleared by the pipelines rendering to them, according to
+  /// their [`PipelineState`]; these defaults keep the clear configuration in one place. They are
+  /// only used by the surface in the following places:
+  ///
+  /// - [`GlutinSurface::default_pipeline_state`] returns a [`PipelineState`] clearing with them,
+  ///   to start the pipelines of every frame from.
+  /// - [`GlutinSurface::frame`] clears its render target with them before rendering.
+  /// - The back buffer is cleared with them after a resize, if enabled (see
+  ///   [`GlutinSurface::set_clear_on_resize`]).
+  ///
+  /// They are not OpenGL state (`glClearColor` and `glClearDepth` are set by each pipeline), so
+  /// they don’t affect anything else: pipelines started with another [`PipelineState`], such as
+  /// [`PipelineState::default`], clear with its values, and raw OpenGL clears use whatever clear
+  /// values the last pipeline set.
+  pub fn set_default_clear(&mut self, color: [f32; 4], depth: f32) {
+    self.default_clear = Some((color, depth));
+  }
+
+  /// Get the default clear color and depth of the surface, if set.
+  pub fn default_clear(&self) -> Option<([f32; 4], f32)> {
+    self.default_clear
+  }
+
+  /// Forget the default clear color and depth of the surface.
+  ///
+  /// [`GlutinSurface::frame`] stops clearing its render target, and
+  /// [`GlutinSurface::default_pipeline_state`] returns [`PipelineState::default`] again.
+  pub fn reset_default_clear(&mut self) {
+    self.default_clear = None;
+  }
+
+  /// Get a [`PipelineState`] clearing with the default clear values of the surface.
+  ///
+  /// If no default clear values are set (see [`GlutinSurface::set_default_clear`]), this is
+  /// [`PipelineState::default`].
+  pub fn default_pipeline_state(&self) -> PipelineState {
+    let state = PipelineState::default();
+
+    match self.default_clear {
+      Some((color, depth)) => state.set_clear_color(color).set_clear_depth(depth),
+      None => state,
+    }
+  }
+
+  /// Get the scale of the internal render target of [`GlutinSurface::frame`].
+  pub fn render_scale(&self) -> f32 {
+    self.render_scale
+  }
+
+  /// Get the size (in physical pixels) of the internal render target of [`GlutinSurface::frame`].
+  pub fn render_size(&self) -> [u32; 2] {
+    let [width, height] = self.size();
+    let scale = |x: u32| ((x as f32 * self.render_scale).round() as u32).max(1);
+
+    [scale(width), scale(height)]
+  }
+
+  /// Render a frame into an internal render target, then present it.
+  ///
+  /// `f` is passed the surface, to create pipeline gates, and the render target, which has a color
+  /// and a depth buffer and is sized according to the render scale (see
+  /// [`GlutinSurface::set_render_scale`]). Once `f` returns, the color buffer of the render target
+  /// is drawn to the whole back buffer with a fullscreen triangle, with linear filtering if the
+  /// render target and the surface have different sizes, and the buffers are swapped (see
+  /// [`GlutinSurface::swap_buffers`]). Drawing instead of blitting works with multisampled back
+  /// buffers as well. The value returned by `f` is returned along with the outcome of the swap.
+  ///
+  /// The render target is recreated when the size of the surface or the render scale changes,
+  /// which loses its content. If the surface is resized while `f` runs (see
+  /// [`GlutinSurface::is_resized_since_back_buffer`]), the frame is still presented with the old
+  /// size, and the swap reports [`SwapResult::Suboptimal`]. If default clear values are set (see
+  /// [`GlutinSurface::set_default_clear`]), the render target is cleared with them before `f` is
+  /// called.
+  pub fn frame<R>(
+    &mut self,
+    f: impl FnOnce(&mut Self, &mut RenderTarget) -> R,
+  ) -> Result<(R, SwapResult), GlutinError> {
+    self.select_draw_buffer(DrawBuffer::Back);
+    self.captured_size_version = self.size_version;
+    let size = self.size();
+    let render_size = self.render_size();
+
+    let mut target = match self.render_target.take() {
+      Some(target) if target.size() == render_size => target,
+
+      // linear minification too, for render scales above 1
+      _ => Framebuffer::new(
+        self,
+        render_size,
+        0,
+        Sampler {
+          min_filter: MinFilter::Linear,
+          ..Sampler::default()
+        },
+      )?,
+    };
+
+    if self.default_clear.is_some() {
+      let state = self.default_pipeline_state();
+      let _ = self
+        .new_pipeline_gate()
+        .pipeline(&target, &state, |_, _| Ok::<_, PipelineError>(()))
+        .into_result();
+    }
+
+    let r = f(self, &mut target);
+    let drawn = self.draw_to_back_buffer(target.color_slot(), size);
+
+    self.render_target = Some(target);
+    drawn?;
+    let swap_result = self.swap_buffers()?;
+
+    Ok((r, swap_result))
+  }
+
+  /// Release all the scratch framebuffers.
+  ///
+  /// See [`GlutinSurface::scratch_framebuffer`].
+  pub fn clear_scratch_framebuffers(&mut self) {
+    self.scratch_framebuffers.clear();
+  }
+
+  /// Set the OpenGL viewport, in physical pixels, and return the previous one.
+  ///
+  /// Viewports are expressed as `[x, y, width, height]`, with the origin in the lower-left corner
+  /// of the framebuffer, and are typically computed with [`GlutinSurface::letterbox_viewport`].
+  /// Returning the previous viewport allows to restore it afterwards.
+  ///
+  /// Pipelines set their own viewport from their [`PipelineState`], so this is mostly useful for
+  /// raw OpenGL rendering; the state cached by luminance is kept in sync.
+  ///
+  /// [`PipelineState`]: luminance::pipeline::PipelineState
+  pub fn set_viewport(&mut self, x: u32, y: u32, width: u32, height: u32) -> [u32; 4] {
+    let mut previous = [0; 4];
+
+    unsafe {
+      gl::GetIntegerv(gl::VIEWPORT, previous.as_mut_ptr());
+      gl::Viewport(x as _, y as _, width as _, height as _);
+      self.gl.state().borrow_mut().invalidate_viewport();
+    }
+
+    previous.map(|v| v.max(0) as u32)
+  }
+
+  /// Set the OpenGL viewport to cover the whole of a framebuffer, and return the previous one.
+  ///
+  /// When rendering with raw OpenGL to a framebuffer whose size differs from the one of the
+  /// surface (e.g. an offscreen pass at a lower resolution), the viewport must match the
+  /// framebuffer, not the window. Like [`GlutinSurface::set_viewport`], the state cached by
+  /// luminance is kept in sync; pipelines set their own viewport anyway.
+  ///
+  /// The viewport is not restored automatically: raw OpenGL rendering following this call, e.g.
+  /// to the back buffer, must restore the previous viewport (with [`GlutinSurface::set_viewport`])
+  /// or set its own.
+  pub fn set_viewport_for_framebuffer<CS, DS>(
+    &mut self,
+    framebuffer: &Framebuffer<GL33, Dim2, CS, DS>,
+  ) -> [u32; 4]
+  where
+    CS: ColorSlot<GL33, Dim2>,
+    DS: DepthStencilSlot<GL33, Dim2>,
+  {
+    let [width, height] = framebuffer.size();
+    self.set_viewport(0, 0, width, height)
+  }
+
+  /// Set the OpenGL scissor region, in physical pixels, and return the previous one.
+  ///
+  /// Regions are expressed as `[x, y, width, height]`, with the origin in the lower-left corner of
+  /// the framebuffer. `None` disables the scissor test. Returning the previous region allows to
+  /// restore it afterwards.
+  ///
+  /// This sets the scissor test directly, regardless of the scissor stack (see
+  /// [`GlutinSurface::push_scissor`]), which overrides it when pushing or popping regions. Render
+  /// gates set their own scissor test from their [`RenderState`], so this is mostly useful for raw
+  /// OpenGL rendering; the state cached by luminance is kept in sync.
+  ///
+  /// [`RenderState`]: luminance::render_state::RenderState
+  pub fn set_scissor(&mut self, region: Option<[u32; 4]>) -> Option<[u32; 4]> {
+    let mut enabled = 0;
+    let mut previous = [0; 4];
+
+    unsafe {
+      gl::GetBooleanv(gl::SCISSOR_TEST, &mut enabled);
+      gl::GetIntegerv(gl::SCISSOR_BOX, previous.as_mut_ptr());
+
+      match region {
+        Some([x, y, width, height]) => {
+          gl::Enable(gl::SCISSOR_TEST);
+          gl::Scissor(x as _, y as _, width as _, height as _);
+        }
+
+        None => gl::Disable(gl::SCISSOR_TEST),
+      }
+
+      let mut state = self.gl.state().borrow_mut();
+      state.invalidate_scissor_state();
+      state.invalidate_scissor_region();
+    }
+
+    if enabled == gl::TRUE {
+      Some(previous.map(|v| v.max(0) as u32))
+    } else {
+      None
+    }
+  }
+
+  /// Push a scissor region, clipping rendering to its intersection with the current one.
+  ///
+  /// Scissor regions are stacked, which is typically used to clip nested UI elements: the pushed
+  /// region is intersected with the current scissor region (if any) and becomes the current one
+  /// until [`GlutinSurface::pop_scissor`] is called. The current region is returned.
+  ///
+  /// The scissor test is enabled with the current region straight away, which affects raw OpenGL
+  /// rendering and clears. Render gates, however, set the scissor test from their [`RenderState`],
+  /// so pass [`GlutinSurface::scissor`] to [`RenderState::set_scissor`] for rendering with
+  /// luminance.
+  ///
+  /// [`RenderState`]: luminance::render_state::RenderState
+  /// [`RenderState::set_scissor`]: luminance::render_state::RenderState::set_scissor
+  pub fn push_scissor(&mut self, region: ScissorRegion) -> ScissorRegion {
+    let region = match self.scissor_stack.last() {
+      Some(current) => current.intersection(&region),
+      None => region,
+    };
+
+    self.scissor_stack.push(region);
+    self.apply_scissor();
+
+    region
+  }
+
+  /// Pop the current scissor region, getting back to the previous one.
+  ///
+  /// The popped region is returned, or `None` if no scissor region was pushed. Once the last region
+  /// is popped, the scissor test is disabled.
+  pub fn pop_scissor(&mut self) -> Option<ScissorRegion> {
+    let region = self.scissor_stack.pop()?;
+    self.apply_scissor();
+
+    Some(region)
+  }
+
+  /// Get the current scissor region, if any.
+  ///
+  /// See [`GlutinSurface::push_scissor`].
+  pub fn scissor(&self) -> Option<ScissorRegion> {
+    self.scissor_stack.last().copied()
+  }
+
+  /// Set the OpenGL scissor state from the current scissor region.
+  fn apply_scissor(&mut self) {
+    unsafe {
+      match self.scissor_stack.last() {
+        Some(region) => {
+          gl::Enable(gl::SCISSOR_TEST);
+          gl::Scissor(
+            region.x as _,
+            region.y as _,
+            region.width as _,
+            region.height as _,
+          );
+        }
+
+        None => gl::Disable(gl::SCISSOR_TEST),
+      }
+
+      // the state cached by luminance is now outdated
+      let mut state = self.gl.state().borrow_mut();
+      state.invalidate_scissor_state();
+      state.invalidate_scissor_region();
+    }
+  }
+
+  /// Read the color buffer of the default framebuffer.
+  ///
+  /// Pixels are returned as RGBA, with one byte per channel, row by row, starting at the
+  /// top-left corner of the surface (top-to-bottom, as images are usually stored); the returned
+  /// vector has `width * height * 4` elements (see [`GlutinSurface::size`]). Use
+  /// [`GlutinSurface::read_back_buffer_into`] to read into an existing buffer instead, and
+  /// [`GlutinSurface::read_back_buffer_raw`] to get the rows in the bottom-to-top order of
+  /// OpenGL.
+  pub fn read_back_buffer(&mut self) -> Result<Vec<u8>, FramebufferError> {
+    let [width, height] = self.size();
+    let mut texels = vec![0; width as usize * height as usize * 4];
+    self.read_back_buffer_into(&mut texels)?;
+
+    Ok(texels)
+  }
+
+  /// Read the color buffer of the default framebuffer into `dst`.
+  ///
+  /// This is the same as [`GlutinSurface::read_back_buffer`], but the pixels are written to a
+  /// caller-provided buffer, which can be reused across frames, for instance for capture. `dst`
+  /// must hold at least `width * height * 4` bytes (see [`GlutinSurface::size`]); only that many
+  /// bytes are written, as RGBA with one byte per channel, row by row, starting at the top-left
+  /// corner of the surface. If `dst` is too small, [`TextureError::NotEnoughPixels`] is returned
+  /// (wrapped in [`FramebufferError::TextureError`]) and nothing is read.
+  ///
+  /// [`TextureError::NotEnoughPixels`]: luminance::texture::TextureError::NotEnoughPixels
+  pub fn read_back_buffer_into(&mut self, dst: &mut [u8]) -> Result<(), FramebufferError> {
+    let row_len = self.size()[0] as usize * 4;
+    let len = self.read_back_buffer_rows(dst)?;
+
+    flip_rows(&mut dst[..len], row_len);
+
+    Ok(())
+  }
+
+  /// Read the color buffer of the default framebuffer, without flipping it.
+  ///
+  /// This is the same as [`GlutinSurface::read_back_buffer`], but rows are returned in the native
+  /// order of OpenGL: starting at the bottom-left corner of the surface (bottom-to-top). This
+  /// avoids flipping the rows twice when the consumer expects bottom-up images, e.g. to write BMP
+  /// files or to upload the pixels to a texture.
+  pub fn read_back_buffer_raw(&mut self) -> Result<Vec<u8>, FramebufferError> {
+    let [width, height] = self.size();
+    let mut texels = vec![0; width as usize * height as usize * 4];
+    self.read_back_buffer_rows(&mut texels)?;
+
+    Ok(texels)
+  }
+
+  /// Read the color buffer of the default framebuffer into `dst`, bottom-to-top, and return the
+  /// number of bytes written.
+  fn read_back_buffer_rows(&mut self, dst: &mut [u8]) -> Result<usize, FramebufferError> {
+    let [width, height] = self.size();
+    let len = width as usize * height as usize * 4;
+
+    if dst.len() < len {
+      return Err(FramebufferError::texture_error(
+        TextureError::not_enough_pixels(len, dst.len()),
+      ));
+    }
+
+    let dst = &mut dst[..len];
+
+    unsafe {
+      gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+      gl::ReadPixels(
+        0,
+        0,
+        width as _,
+        height as _,
+        gl::RGBA,
+        gl::UNSIGNED_BYTE,
+        dst.as_mut_ptr() as *mut c_void,
+      );
+    }
+
+    Ok(len)
+  }
+
+  /// Read a single pixel of the color buffer of the default framebuffer.
+  ///
+  /// This is meant for GPU-based object picking: render object identifiers as colors, then read
+  /// the pixel under the cursor. `x` and `y` are in physical pixels, starting at the top-left
+  /// corner of the surface, like the cursor positions reported by winit; the pixel is returned as
+  /// RGBA, with one byte per channel. Only that pixel is transferred. If it lies outside of the
+  /// surface (see [`GlutinSurface::size`]), [`TextureError::CannotRetrieveTexels`] is returned
+  /// (wrapped in [`FramebufferError::TextureError`]).
+  ///
+  /// Reading back stalls the pipeline: the CPU waits for the GPU to finish rendering the frame.
+  /// This is fine for occasional reads (e.g. on click), but reading every frame, e.g. for hover
+  /// feedback, caps the frame rate; read a frame late with a pixel buffer object (see
+  /// [`GlutinSurface::with_raw_gl`]) instead.
+  ///
+  /// [`TextureError::CannotRetrieveTexels`]: luminance::texture::TextureError::CannotRetrieveTexels
+  pub fn read_pixel(&mut self, x: u32, y: u32) -> Result<[u8; 4], FramebufferError> {
+    let [width, height] = self.size();
+
+    if x >= width || y >= height {
+      return Err(FramebufferError::texture_error(
+        TextureError::cannot_retrieve_texels(format!(
+          "pixel ({}, {}) out of the {}×{} surface",
+          x, y, width, height
+        )),
+      ));
+    }
+
+    let mut pixel = [0; 4];
+
+    unsafe {
+      gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+      gl::ReadPixels(
+        x as _,
+        (height - 1 - y) as _,
+        1,
+        1,
+        gl::RGBA,
+        gl::UNSIGNED_BYTE,
+        pixel.as_mut_ptr() as *mut c_void,
+      );
+    }
+
+    Ok(pixel)
+  }
+
+  /// Read the depth buffer of the default framebuffer.
+  ///
+  /// Depth values are returned row by row, starting at the top-left corner of the surface, with one
+  /// `f32` per pixel, so the returned vector has `width * height` elements (see
+  /// [`GlutinSurface::size`]).
+  ///
+  /// This requires a depth buffer to have been allocated along with the default framebuffer (see
+  /// [`ContextBuilder::with_depth_buffer`], which you can set with
+  /// [`GlutinSurface::new_gl33_from_builders`]). If the surface has no depth buffer,
+  /// [`FramebufferError::UnsupportedAttachment`] is returned.
+  pub fn read_depth_buffer(&mut self) -> Result<Vec<f32>, FramebufferError> {
+    if self.ctx.get_pixel_format().depth_bits == 0 {
+      return Err(FramebufferError::unsupported_attachment());
+    }
+
+    let [width, height] = self.size();
+    let mut depth = vec![0.; width as usize * height as usize];
+
+    unsafe {
+      gl::BindFramebuffer(gl::READ_FRAMEBUFFER, 0);
+      gl::ReadPixels(
+        0,
+        0,
+        width as _,
+        height as _,
+        gl::DEPTH_COMPONENT,
+        gl::FLOAT,
+        depth.as_mut_ptr() as *mut c_void,
+      );
+    }
+
+    flip_rows(&mut depth, width as usize);
+
+    Ok(depth)
+  }
+
+  /// Block until all the pending GPU work is done.
+  ///
+  /// This is a startup hitch-reduction helper: call it once after creating your initial resources
+  /// (shader programs, textures, etc.) so that the driver finishes compiling and uploading them
+  /// before the first real frame, instead of stalling the first frames that use them.
+  ///
+  /// This issues a `glFinish`, so avoid calling it in your render loop.
+  pub fn flush_pending_gpu_work(&mut self) {
+    unsafe { gl::Finish() };
+  }
+
+  /// Render a frame with `f`, and block until the GPU has completed it.
+  ///
+  /// After `f` returns, a fence is inserted in the command stream (`glFenceSync`) and waited for
+  /// (`glClientWaitSync`), so that all the commands issued by `f` have completed when this method
+  /// returns. This is the building block of frame-accurate offscreen exports (e.g. feeding a video
+  /// encoder at a fixed rate): read the rendered frame back right after (see
+  /// [`GlutinSurface::read_back_buffer`]) without stalling on the GPU. The buffers are not
+  /// swapped.
+  ///
+  /// This removes any overlap between the CPU and the GPU: the CPU waits while the GPU renders,
+  /// then the GPU idles while the CPU prepares the next frame, which lowers the throughput. Use
+  /// it for exports, not in an interactive render loop.
+  ///
+  /// If the GPU ran out of memory while rendering the frame, [`GlutinError::OutOfMemory`] is
+  /// returned, and other OpenGL errors are returned as [`GlutinError::GlError`]; if the fence
+  /// cannot be created or waiting for it fails (e.g. because the context was lost),
+  /// [`GlutinError::FenceFailed`] is.
+  pub fn render_frame_blocking<R>(
+    &mut self,
+    f: impl FnOnce(&mut Self) -> R,
+  ) -> Result<R, GlutinError> {
+    let r = f(self);
+
+    let signaled = unsafe {
+      let fence = gl::FenceSync(gl::SYNC_GPU_COMMANDS_COMPLETE, 0);
+
+      if fence.is_null() {
+        false
+      } else {
+        // the first wait flushes the commands, so that the fence is eventually signaled
+        let mut flags = gl::SYNC_FLUSH_COMMANDS_BIT;
+        let signaled = loop {
+          match gl::ClientWaitSync(fence, flags, FENCE_WAIT_TIMEOUT_NS) {
+            gl::ALREADY_SIGNALED | gl::CONDITION_SATISFIED => break true,
+            gl::TIMEOUT_EXPIRED => flags = 0,
+            _ => break false,
+          }
+        };
+
+        gl::DeleteSync(fence);
+        signaled
+      }
+    };
+
+    let gl_errors = take_gl_errors()?;
+
+    if !gl_errors.is_empty() {
+      return Err(GlutinError::GlError(gl_errors));
+    }
+
+    if !signaled {
+      return Err(GlutinError::FenceFailed);
+    }
+
+    Ok(r)
+  }
+
+  /// Run raw OpenGL code.
+  ///
+  /// The OpenGL context of the surface is made current before running `f`, and the luminance
+  /// graphics state cache is invalidated afterwards (see
+  /// [`GlutinSurface::invalidate_gl_state_cache`]), so that luminance doesn’t rely on cached values
+  /// that `f` might have changed behind its back.
+  ///
+  /// `f` must not leave any OpenGL error pending and must not delete objects owned by luminance
+  /// (buffers, textures, framebuffers, shader programs, etc.).
+  pub fn with_raw_gl<R>(&mut self, f: impl FnOnce() -> R) -> Result<R, GlutinError> {
+    self.make_current()?;
+    let r = f();
+    self.invalidate_gl_state_cache();
+
+    Ok(r)
+  }
+
+  /// Reset the pipeline state to the defaults luminance expects, and synchronize its state cache.
+  ///
+  /// Use this after giving control to third-party OpenGL code (UI libraries, etc.) that might
+  /// leave the pipeline state in an unexpected configuration. The state is reset to match
+  /// [`RenderState::default`], plus a viewport covering the whole surface:
+  ///
+  /// - Blending is disabled, with the `FUNC_ADD` equation and the `ONE` / `ZERO` factors.
+  /// - The depth test is enabled with the `LESS` comparison, and depth writes are enabled.
+  /// - The stencil test is disabled.
+  /// - Face culling is disabled.
+  /// - The scissor test is disabled, and the scissor stack (see [`GlutinSurface::push_scissor`])
+  ///   is emptied.
+  /// - The viewport covers the whole surface.
+  ///
+  /// The luminance state cache is then invalidated (see
+  /// [`GlutinSurface::invalidate_gl_state_cache`]).
+  ///
+  /// [`RenderState::default`]: luminance::render_state::RenderState
+  pub fn reset_pipeline_state(&mut self) {
+    let [width, height] = self.size();
+    self.scissor_stack.clear();
+
+    unsafe {
+      gl::Disable(gl::BLEND);
+      gl::BlendEquation(gl::FUNC_ADD);
+      gl::BlendFunc(gl::ONE, gl::ZERO);
+
+      gl::Enable(gl::DEPTH_TEST);
+      gl::DepthFunc(gl::LESS);
+      gl::DepthMask(gl::TRUE);
+
+      gl::Disable(gl::STENCIL_TEST);
+      gl::Disable(gl::CULL_FACE);
+      gl::Disable(gl::SCISSOR_TEST);
+
+      gl::Viewport(0, 0, width as _, height as _);
+    }
+
+    self.invalidate_gl_state_cache();
+  }
+
+  /// Map an OpenGL buffer for direct CPU access.
+  ///
+  /// `buffer` is the OpenGL name of a buffer (as returned by `glGenBuffers`) and `f` is passed its
+  /// whole content, which it can read and write; writes are visible to the GPU once `f` returns.
+  /// The context of the surface is made current first.
+  ///
+  /// This is meant for buffers created with raw OpenGL code (see [`GlutinSurface::with_raw_gl`]),
+  /// e.g. buffers shared with a third-party library. luminance doesn’t expose the OpenGL names of
+  /// its own buffers, so they cannot be passed here; map the buffers of a [`Tess`] with its
+  /// `vertices_mut`, `indices_mut` and `instances_mut` methods instead.
+  ///
+  /// The buffer is mapped with `glMapBufferRange`. Persistent mapping (`GL_ARB_buffer_storage`)
+  /// requires buffers allocated with immutable storage, which OpenGL 3.3 buffers are not, so the
+  /// buffer is mapped for the duration of `f` only. If the buffer cannot be mapped, the error
+  /// raised by the mapping is cleared, and its content is copied to memory with
+  /// `glGetBufferSubData` and written back with `glBufferSubData` instead. The buffer is unmapped
+  /// and unbound even if `f` panics; in the fallback, the writes of `f` are then lost.
+  ///
+  /// [`Tess`]: luminance::tess::Tess
+  ///
+  /// # Synchronization
+  ///
+  /// Mapping a buffer waits for the GPU to finish the commands using it, which stalls the CPU if
+  /// the buffer was used by the current frame. For streaming geometry, cycle through several
+  /// buffers, so that the mapped one is not in use anymore.
+  ///
+  /// # Safety
+  ///
+  /// `buffer` must be the name of a live buffer of the context of the surface, which must not be
+  /// mapped already.
+  pub unsafe fn with_mapped_buffer<R>(
+    &mut self,
+    buffer: gl::types::GLuint,
+    f: impl FnOnce(&mut [u8]) -> R,
+  ) -> Result<R, GlutinError> {
+    use std::slice;
+
+    self.make_current()?;
+
+    // use the copy write target, which luminance doesn’t cache, to keep its bindings untouched
+    let mut size = 0;
+    gl::BindBuffer(gl::COPY_WRITE_BUFFER, buffer);
+    let mut guard = MappedBufferGuard { mapped: false };
+    gl::GetBufferParameteriv(gl::COPY_WRITE_BUFFER, gl::BUFFER_SIZE, &mut size);
+    let size = size.max(0) as usize;
+
+    // empty buffers cannot be mapped
+    let ptr = if size == 0 {
+      ptr::null_mut()
+    } else {
+      gl::MapBufferRange(
+        gl::COPY_WRITE_BUFFER,
+        0,
+        size as _,
+        gl::MAP_READ_BIT | gl::MAP_WRITE_BIT,
+      ) as *mut u8
+    };
+
+    let r = if ptr.is_null() {
+      if size != 0 {
+        // the error raised by glMapBufferRange
+        gl::GetError();
+      }
+
+      let mut bytes = vec![0; size];
+      gl::GetBufferSubData(
+        gl::COPY_WRITE_BUFFER,
+        0,
+        size as _,
+        bytes.as_mut_ptr() as *mut c_void,
+      );
+
+      let r = f(&mut bytes);
+
+      gl::BufferSubData(
+        gl::COPY_WRITE_BUFFER,
+        0,
+        size as _,
+        bytes.as_ptr() as *const c_void,
+      );
+      r
+    } else {
+      guard.mapped = true;
+      f(slice::from_raw_parts_mut(ptr, size))
+    };
+
+    drop(guard);
+
+    Ok(r)
+  }
+
+  /// Invalidate the luminance graphics state cache.
+  ///
+  /// luminance caches the OpenGL state to prevent issuing redundant OpenGL calls. If you change the
+  /// OpenGL state without using luminance (third-party libraries, raw OpenGL calls, etc.), you must
+  /// call this method so that the next luminance calls apply their state again.
+  pub fn invalidate_gl_state_cache(&mut self) {
+    let mut state = unsafe { self.gl.state() }.borrow_mut();
+
+    state.invalidate_vertex_array();
+    state.invalidate_array_buffer();
+    state.invalidate_shader_program();
+    state.invalidate_framebuffer();
+    state.invalidate_element_array_buffer();
+    state.invalidate_texture_unit();
+    state.invalidate_bound_textures();
+    state.invalidate_bound_uniform_buffers();
+    state.invalidate_viewport();
+    state.invalidate_clear_color();
+    state.invalidate_clear_depth();
+    state.invalidate_clear_stencil();
+    state.invalidate_blending_state();
+    state.invalidate_blending_equation();
+    state.invalidate_blending_func();
+    state.invalidate_depth_test();
+    state.invalidate_depth_test_comparison();
+    state.invalidate_depth_write();
+    state.invalidate_stencil_test_enabled();
+    state.invalidate_stencil_test();
+    state.invalidate_stencil_operations();
+    state.invalidate_face_culling_state();
+    state.invalidate_face_culling_order();
+    state.invalidate_face_culling_mode();
+    state.invalidate_scissor_state();
+    state.invalidate_scissor_region();
+    state.invalidate_vertex_restart();
+    state.invalidate_patch_vertex_nb();
+    state.invalidate_srgb_framebuffer_enabled();
+  }
+
+  /// Check whether the OpenGL context of the surface is current on the calling thread.
+  ///
+  /// This asks the platform for the context current on the calling thread
+  /// (`eglGetCurrentContext`, `glXGetCurrentContext`, `wglGetCurrentContext`, etc.), so it also
+  /// detects contexts made current behind the back of the surface. See the thread affinity rules
+  /// of [`GlutinSurface`].
+  pub fn current_thread_owns_context(&self) -> bool {
+    self.ctx.is_current()
+  }
+
+  /// Make the OpenGL context of the surface current, if it is not already.
+  ///
+  /// A thread has at most one current context, so when several contexts are used on the same
+  /// thread (with other windowing libraries, or raw glutin contexts), the context of the surface
+  /// must be made current again before using the surface. In debug builds, accessing the backend
+  /// of the surface (which every luminance call does) while its context is not current panics.
+  pub fn make_current(&mut self) -> Result<(), ContextError> {
+    if self.ctx.is_current() {
+      return Ok(());
+    }
+
+    #[cfg(feature = "profiling")]
+    profiling::scope!("make_current");
+
+    // making a context current consumes it and always gives it back, even on failure, so we can
+    // temporarily move it out of the surface; if glutin panics in the meantime, the surface holds a
+    // bitwise copy of a context that might be dropped already, so the process is aborted instead of
+    // unwinding
+    let guard = AbortOnUnwind;
+
+    let r = unsafe {
+      let (ctx, r) = match ptr::read(&self.ctx).make_current() {
+        Ok(ctx) => (ctx, Ok(())),
+        Err((ctx, e)) => (ctx, Err(e)),
+      };
+
+      ptr::write(&mut self.ctx, ctx);
+      r
+    };
+
+    std::mem::forget(guard);
+    r
+  }
+
+  /// Get the platform API providing the OpenGL context of the surface.
+  pub fn windowing_backend(&self) -> WindowingBackend {
+    windowing::windowing_backend(&self.ctx)
+  }
+
+  /// Get the GLSL version of the OpenGL context, as `(major, minor)`, e.g. `(4, 60)`.
+  ///
+  /// This is parsed from `GL_SHADING_LANGUAGE_VERSION`, and checked to be at least 3.30 when the
+  /// surface is created (see [`GlutinError::UnsupportedGlslVersion`]).
+  pub fn glsl_version(&self) -> (u16, u16) {
+    self.glsl_version
+  }
+
+  /// Get the known bugs of the driver.
+  pub fn quirks(&self) -> &QuirkSet {
+    &self.quirks
+  }
+
+  /// Check whether rendering is hardware accelerated.
+  ///
+  /// Drivers sometimes silently fall back to software rendering (e.g. when the GPU driver is not
+  /// installed, or in virtual machines and containers), which tanks performance. Rendering is
+  /// considered hardware accelerated if the pixel format of the context is, and if the OpenGL
+  /// renderer string doesn’t designate a known software rasterizer (Mesa’s llvmpipe, softpipe and
+  /// swrast, SwiftShader, the Microsoft Basic Render Driver, GDI Generic and the Apple Software
+  /// Renderer).
+  ///
+  /// Detection is heuristic: an unknown software rasterizer is reported as hardware accelerated.
+  pub fn is_hardware_accelerated(&self) -> bool {
+    let renderer = unsafe { self.gl.state() }.borrow_mut().get_renderer_name();
+    self.ctx.get_pixel_format().hardware_accelerated && !quirks::is_software_renderer(&renderer)
+  }
+
+  /// Get the flags of the OpenGL context.
+  pub fn context_flags(&self) -> ContextFlags {
+    self.context_flags
+  }
+
+  /// Get the vertical synchronization of the surface.
+  ///
+  /// The swap interval cannot be reliably read back from the platform, so this is the value that
+  /// was last requested — when creating the surface, with [`GlutinSurface::set_present_mode`] or
+  /// [`GlutinSurface::with_vsync_disabled`] — which the driver might not honor (drivers often
+  /// allow users to force vsync on or off).
+  pub fn swap_interval(&self) -> Vsync {
+    Vsync::from_bool(self.present_mode != PresentMode::Immediate)
+  }
+
+  /// Get the presentation mode of the surface.
+  ///
+  /// As with [`GlutinSurface::swap_interval`], this is the mode that was last requested, which
+  /// the driver might not honor.
+  pub fn present_mode(&self) -> PresentMode {
+    self.present_mode
+  }
+
+  /// Set the presentation mode of the surface, and return the effective one.
+  ///
+  /// Presentation modes are mapped to the best available swap interval:
+  ///
+  /// - [`PresentMode::Immediate`] and [`PresentMode::Fifo`] are always available, provided the
+  ///   swap interval can be changed.
+  /// - [`PresentMode::FifoRelaxed`] requires `WGL_EXT_swap_control_tear` or
+  ///   `GLX_EXT_swap_control_tear`, and falls back to [`PresentMode::Fifo`].
+  /// - [`PresentMode::Mailbox`] always falls back to [`PresentMode::Fifo`].
+  ///
+  /// glutin can only set the swap interval when creating a context, so it is changed with the
+  /// swap control extensions of the platform: `WGL_EXT_swap_control` on Windows,
+  /// `GLX_EXT_swap_control`, `GLX_MESA_swap_control` or `GLX_SGI_swap_control` on X11 and
+  /// `eglSwapInterval` with EGL (see [`GlutinSurface::force_swap_control`] to pick one). If none
+  /// is available (e.g. on macOS), the presentation mode is left untouched, and the current one is
+  /// returned.
+  pub fn set_present_mode(&mut self, mode: PresentMode) -> PresentMode {
+    let relaxed = mode == PresentMode::FifoRelaxed && self.set_swap_interval(-1);
+
+    let effective = if relaxed {
+      Some(PresentMode::FifoRelaxed)
+    } else if mode == PresentMode::Immediate {
+      self.set_swap_interval(0).then(|| PresentMode::Immediate)
+    } else {
+      self.set_swap_interval(1).then(|| PresentMode::Fifo)
+    };
+
+    if let Some(effective) = effective {
+      self.present_mode = effective;
+      // keep vsync when the surface is recreated
+      self.params.ctx_builder.gl_attr.vsync = effective != PresentMode::Immediate;
+    }
+
+    self.present_mode
+  }
+
+  /// Run `f` with vertical synchronization disabled, and restore it afterwards.
+  ///
+  /// This is useful to render frames as fast as possible for a while, e.g. to keep a loading
+  /// screen animated while loading assets. The presentation mode is switched to
+  /// [`PresentMode::Immediate`] (see [`GlutinSurface::set_present_mode`] for the platform
+  /// support), and restored once `f` returns. If the platform doesn’t allow it, `f` runs with the
+  /// presentation mode left untouched.
+  pub fn with_vsync_disabled<R>(&mut self, f: impl FnOnce(&mut Self) -> R) -> R {
+    let saved = self.present_mode;
+    let disabled = saved != PresentMode::Immediate
+      && self.set_present_mode(PresentMode::Immediate) == PresentMode::Immediate;
+
+    let r = f(self);
+
+    if disabled {
+      self.set_present_mode(saved);
+    }
+
+    r
+  }
+
+  /// Change the swap interval of the context, returning whether the platform allowed it.
+  fn set_swap_interval(&mut self, interval: i32) -> bool {
+    let control =
+      unsafe { swap_control::set_swap_interval(&self.ctx, interval, self.forced_swap_control) };
+
+    if control.is_some() {
+      self.swap_control = control;
+    }
+
+    control.is_some()
+  }
+
+  /// Force the mechanism changing the swap interval, or let the surface pick the best available
+  /// one with `None` (the default).
+  ///
+  /// The swap interval is changed with the first available swap control extension of the
+  /// platform (see [`GlutinSurface::set_present_mode`]), which is what most applications want.
+  /// Drivers have bugs specific to some of these extensions, though, so this forces a given
+  /// mechanism; see [`SwapControl`] for their differences. If the forced mechanism is not
+  /// available, the swap interval cannot be changed anymore.
+  ///
+  /// The current presentation mode is applied again with the new mechanism; check
+  /// [`GlutinSurface::swap_control`] afterwards to know whether it succeeded.
+  pub fn force_swap_control(&mut self, control: Option<SwapControl>) {
+    self.forced_swap_control = control;
+    self.swap_control = None;
+    self.set_present_mode(self.present_mode);
+  }
+
+  /// Get the mechanism that last changed the swap interval.
+  ///
+  /// `None` is returned if the swap interval was never changed since the surface was created (or
+  /// since [`GlutinSurface::force_swap_control`] was called), or if it couldn’t be changed.
+  pub fn swap_control(&self) -> Option<SwapControl> {
+    self.swap_control
+  }
+
+  /// Get the implementation limits of the OpenGL context.
+  pub fn limits(&self) -> GlLimits {
+    self.limits
+  }
+
+  /// Check whether the OpenGL context supports an extension, such as `GL_ARB_gpu_shader_fp64`.
+  pub fn has_extension(&self, name: &str) -> bool {
+    self.extensions.contains(name)
+  }
+
+  /// Check whether the OpenGL context supports multiview rendering (`GL_OVR_multiview`).
+  ///
+  /// Multiview rendering renders into several layers of an array texture in a single pass, which
+  /// is typically used for stereo (VR) rendering or to render all the faces of a cubemap at once.
+  ///
+  /// This is a capability query only: luminance framebuffers don’t support multiview attachments
+  /// yet, so setting up multiview rendering requires raw OpenGL (see
+  /// [`GlutinSurface::with_raw_gl`]), attaching the layers of a texture with
+  /// `glFramebufferTextureMultiviewOVR` and declaring `layout(num_views = N) in;` in the vertex
+  /// shader.
+  pub fn supports_multiview(&self) -> bool {
+    self.has_extension("GL_OVR_multiview")
+  }
+
+  /// Get the maximum anisotropy of texture filtering (`GL_MAX_TEXTURE_MAX_ANISOTROPY`).
+  ///
+  /// `None` is returned if anisotropic filtering is not supported
+  /// (`GL_EXT_texture_filter_anisotropic`, or `GL_ARB_texture_filter_anisotropic`, core since
+  /// OpenGL 4.6). Anisotropy levels passed to the samplers of textures should be clamped to this
+  /// value.
+  pub fn max_anisotropy(&self) -> Option<f32> {
+    if !self.has_extension("GL_EXT_texture_filter_anisotropic")
+      && !self.has_extension("GL_ARB_texture_filter_anisotropic")
+    {
+      return None;
+    }
+
+    let mut max_anisotropy = 0.;
+    unsafe { gl::GetFloatv(MAX_TEXTURE_MAX_ANISOTROPY, &mut max_anisotropy) };
+
+    Some(max_anisotropy)
+  }
+
+  /// Check whether the OpenGL context can compile shaders in parallel
+  /// (`GL_KHR_parallel_shader_compile` or `GL_ARB_parallel_shader_compile`).
+  pub fn supports_parallel_shader_compile(&self) -> bool {
+    self.has_extension("GL_KHR_parallel_shader_compile")
+      || self.has_extension("GL_ARB_parallel_shader_compile")
+  }
+
+  /// Set the maximum number of threads the driver uses to compile shaders, returning whether the
+  /// driver supports it.
+  ///
+  /// This calls `glMaxShaderCompilerThreadsKHR` (or its ARB equivalent), which applies to the
+  /// whole context. `0` disables parallel compilation, and `u32::MAX` lets the driver pick the
+  /// number of threads, which is what drivers do by default. If parallel compilation is not
+  /// supported (see [`GlutinSurface::supports_parallel_shader_compile`]), this is a no-op and
+  /// `false` is returned.
+  ///
+  /// luminance checks the link status of a program as soon as it is linked, which waits for its
+  /// compilation, so this doesn’t make creating a luminance program asynchronous. It helps when
+  /// compiling raw OpenGL programs (see [`GlutinSurface::with_raw_gl`]): after linking, poll
+  /// `glGetProgramiv(program, GL_COMPLETION_STATUS_KHR, …)` (`0x91B1`) every frame, and only
+  /// check the link status once it returns `GL_TRUE`, to keep rendering while shaders compile.
+  pub fn set_shader_compile_threads(&self, threads: u32) -> bool {
+    if !self.supports_parallel_shader_compile() {
+      return false;
+    }
+
+    let max_threads = [
+      "glMaxShaderCompilerThreadsKHR",
+      "glMaxShaderCompilerThreadsARB",
+    ]
+    .iter()
+    .map(|name| self.ctx.get_proc_address(name))
+    .find(|f| !f.is_null());
+
+    match max_threads {
+      Some(max_threads) => {
+        unsafe {
+          let max_threads: extern "system" fn(gl::types::GLuint) = std::mem::transmute(max_threads);
+          max_threads(threads);
+        }
+
+        true
+      }
+
+      None => false,
+    }
+  }
+
+  /// Get an estimate of the GPU memory usage.
+  ///
+  /// This is best-effort and relies on vendor extensions:
+  ///
+  /// - `GL_NVX_gpu_memory_info`, exposed by NVIDIA drivers, reports both the total and available
+  ///   amounts of dedicated memory.
+  /// - `GL_ATI_meminfo`, exposed by AMD drivers, only reports the available amount of memory
+  ///   (for textures).
+  ///
+  /// `None` is returned if none of them is supported, which is the case of most other drivers.
+  pub fn gpu_memory_info(&self) -> Option<GpuMemoryInfo> {
+    if self.has_extension("GL_NVX_gpu_memory_info") {
+      let mut total = 0;
+      let mut available = 0;
+
+      unsafe {
+        gl::GetIntegerv(GPU_MEMORY_INFO_TOTAL_AVAILABLE_MEMORY_NVX, &mut total);
+        gl::GetIntegerv(GPU_MEMORY_INFO_CURRENT_AVAILABLE_VIDMEM_NVX, &mut available);
+      }
+
+      Some(GpuMemoryInfo {
+        total_kb: Some(total as u32),
+        available_kb: available as u32,
+      })
+    } else if self.has_extension("GL_ATI_meminfo") {
+      // free memory, largest free block, free auxiliary memory, largest free auxiliary block
+      let mut info = [0; 4];
+      unsafe { gl::GetIntegerv(TEXTURE_FREE_MEMORY_ATI, info.as_mut_ptr()) };
+
+      Some(GpuMemoryInfo {
+        total_kb: None,
+        available_kb: info[0] as u32,
+      })
+    } else {
+      None
+    }
+  }
+
+  /// Enable or disable the measurement of the time between buffer swaps.
+  ///
+  /// When enabled, [`GlutinSurface::swap_buffers`] records the time elapsed since the previous swap,
+  /// and [`GlutinSurface::swap_timing_stats`] reports statistics about those gaps. A steady mean
+  /// with a high jitter (standard deviation) or spikes (maximum) hints at stutter. Enabling resets
+  /// the statistics. This is disabled by default, and costs only a branch per swap when disabled.
+  pub fn enable_swap_timing(&mut self, enabled: bool) {
+    self.swap_timing = if enabled {
+      Some(SwapTiming::default())
+    } else {
+      None
+    };
+  }
+
+  /// Get statistics about the time between buffer swaps.
+  ///
+  /// Statistics are empty if swap timing is disabled (see [`GlutinSurface::enable_swap_timing`]).
+  pub fn swap_timing_stats(&self) -> SwapTimingStats {
+    self
+      .swap_timing
+      .as_ref()
+      .map(SwapTiming::stats)
+      .unwrap_or_default()
+  }
+
+  /// Set a frame budget, calling `on_overrun` for each frame exceeding it.
+  ///
+  /// A frame lasts from the end of a call to [`GlutinSurface::swap_buffers`] (or
+  /// [`GlutinSurface::frame`], which swaps the buffers) to the start of the next one, i.e. the time
+  /// spent producing the frame, excluding waiting for vertical synchronization or the background
+  /// frame rate cap (see [`GlutinSurface::set_background_fps`]). When it exceeds `budget`,
+  /// `on_overrun` is called with the measured duration, right before swapping; log it, or count
+  /// overruns, to catch performance regressions during development. The first frame is measured
+  /// from the first swap after setting the budget.
+  ///
+  /// Setting a budget replaces the previous one. When no budget is set (the default), this costs
+  /// only a branch per swap.
+  pub fn set_frame_budget(
+    &mut self,
+    budget: std::time::Duration,
+    on_overrun: impl FnMut(std::time::Duration) + 'static,
+  ) {
+    self.frame_budget = Some(FrameBudget::new(budget, Box::new(on_overrun)));
+  }
+
+  /// Remove the frame budget, if any; see [`GlutinSurface::set_frame_budget`].
+  pub fn remove_frame_budget(&mut self) {
+    self.frame_budget = None;
+  }
+
+  /// Set the frame rate cap applied while the window is in the background.
+  ///
+  /// The window is in the background when it has lost the input focus (see
+  /// [`GlutinSurface::is_focused`]) or is minimized (see [`GlutinSurface::is_minimized`]); both are
+  /// tracked by [`GlutinSurface::handle_window_event`], which must be passed the window events.
+  /// While in the background, [`GlutinSurface::swap_buffers`] sleeps before swapping so that
+  /// frames are at least `1 / fps` seconds apart, which keeps background animations alive at a
+  /// fraction of the CPU and GPU cost. The cap is lifted as soon as the window is back in the
+  /// foreground.
+  ///
+  /// This is the only frame rate cap of the surface; in the foreground, the frame rate is only
+  /// limited by vertical synchronization (see [`GlutinSurface::set_present_mode`]). In the
+  /// background, the lowest of the cap and the refresh rate wins. `None`, the default, disables
+  /// the cap, and `Some(0)` is treated as `Some(1)`.
+  pub fn set_background_fps(&mut self, fps: Option<u32>) {
+    self.background_fps = fps;
+  }
+
+  /// Check whether the window has the input focus.
+  ///
+  /// This is tracked by [`GlutinSurface::handle_window_event`], and assumed when the window is
+  /// created.
+  pub fn is_focused(&self) -> bool {
+    self.focused
+  }
+
+  /// Get the theme of the window (light or dark), if known.
+  ///
+  /// This is tracked by [`GlutinSurface::handle_window_event`], from
+  /// [`WindowEvent::ThemeChanged`].
+  ///
+  /// # Platform-specific
+  ///
+  /// - **Windows:** the theme is known from the creation of the window, and kept up to date.
+  /// - **macOS, X11, Wayland:** winit doesn’t report the theme; `None` is returned.
+  pub fn theme(&self) -> Option<Theme> {
+    self.theme
+  }
+
+  /// Whether the window is in the background, i.e. unfocused or minimized.
+  fn is_in_background(&self) -> bool {
+    !self.focused || self.minimized.get()
+  }
+
+  /// Get the time at which the last frame was presented, relative to the creation of the surface.
+  ///
+  /// This is updated by [`GlutinSurface::swap_buffers`], and is meant to drive animations: unlike
+  /// sampling [`Instant::now`](std::time::Instant::now) at arbitrary points of the frame, it
+  /// ticks at the pace of the monitor, which reduces judder. `None` is returned until the first
+  /// swap.
+  ///
+  /// The time comes from the presentation timing feedback of the platform when available, i.e.
+  /// the time of the last vertical retrace reported by `GLX_OML_sync_control` on X11 (Mesa and
+  /// the proprietary NVIDIA driver). Everywhere else (Windows, macOS, Wayland and EGL), or if the
+  /// driver stops reporting it, it falls back to the monotonic clock sampled right after
+  /// swapping, which is as accurate as the swap is blocking (i.e. with vsync enabled). The time
+  /// never goes backwards, even when the surface is recreated.
+  pub fn presentation_time(&self) -> Option<std::time::Duration> {
+    self.presentation_clock.last()
+  }
+
+  /// Get the time the last call to [`GlutinSurface::swap_buffers`] spent blocked in the platform
+  /// swap.
+  ///
+  /// Only the swap itself is measured, excluding the error checks and the background frame rate
+  /// cap (see [`GlutinSurface::set_background_fps`]). With vertical synchronization, the swap
+  /// blocks until the driver can queue another frame, so a latency steadily close to the refresh
+  /// period means the application waits on vsync or on the GPU, while a latency close to zero means
+  /// it is CPU-bound. Some drivers return immediately and block on a later OpenGL call instead, so
+  /// this is a hint rather than an exact measurement.
+  ///
+  /// Zero is returned until the first successful swap.
+  pub fn last_present_latency(&self) -> std::time::Duration {
+    self.present_latency
+  }
+
+  /// Swap the back and front buffers.
+  ///
+  /// Before swapping, the pending OpenGL errors are taken: if the GPU ran out of memory while
+  /// rendering the frame, [`GlutinError::OutOfMemory`] is returned and the buffers are not
+  /// swapped. Other errors don’t prevent presenting the frame: once the buffers are swapped, they
+  /// are returned as [`GlutinError::GlError`], unless the surface or the context was lost, which
+  /// is reported instead. The frame was presented when [`GlutinError::GlError`] is returned, so
+  /// you can log it and go on rendering.
+  ///
+  /// Failures to present that can be recovered from (by recreating the surface) are reported as a
+  /// [`SwapResult`], as well as frames rendered with an outdated size; see its variants for the
+  /// recommended responses. Other errors are returned as a [`GlutinError`].
+  ///
+  /// With the `profiling` feature enabled, a frame marker is emitted after swapping, so that frames
+  /// show up in the profiler enabled through the [profiling](https://crates.io/crates/profiling)
+  /// crate.
+  pub fn swap_buffers(&mut self) -> Result<SwapResult, GlutinError> {
+    let gl_errors = take_gl_errors()?;
+
+    if self.quirks.needs_explicit_flush_before_swap {
+      unsafe { gl::Flush() };
+    }
+
+    if let Some(ref mut frame_budget) = self.frame_budget {
+      frame_budget.end_frame();
+    }
+
+    let fps_cap = if self.is_in_background() {
+      self.background_fps
+    } else {
+      None
+    };
+    self.frame_limiter.wait(fps_cap);
+
+    {
+      #[cfg(feature = "profiling")]
+      profiling::scope!("swap_buffers");
+
+      let swap_start = std::time::Instant::now();
+
+      match self.ctx.swap_buffers() {
+        Ok(()) => self.present_latency = swap_start.elapsed(),
+        Err(ContextError::ContextLost) => return Ok(SwapResult::ContextLost),
+        Err(ContextError::OsError(_)) | Err(ContextError::IoError(_)) => {
+          return Ok(SwapResult::SurfaceLost)
+        }
+        Err(e) => return Err(e.into()),
+      }
+    }
+
+    let result = if self.is_resized_since_back_buffer() {
+      SwapResult::Suboptimal
+    } else {
+      SwapResult::Ok
+    };
+    // the next frame starts with the current size
+    self.captured_size_version = self.size_version;
+
+    if let Some(ref mut swap_timing) = self.swap_timing {
+      swap_timing.record();
+    }
+
+    if let Some(ref mut frame_budget) = self.frame_budget {
+      frame_budget.start_frame();
+    }
+
+    let feedback = if self.presentation_clock.wants_feedback() {
+      unsafe { sync_control::last_retrace_time(&self.ctx) }
+    } else {
+      None
+    };
+    self.presentation_clock.record(feedback);
+
+    #[cfg(feature = "profiling")]
+    profiling::finish_frame!();
+
+    if !gl_errors.is_empty() {
+      return Err(GlutinError::GlError(gl_errors));
+    }
+
+    Ok(result)
+  }
+}
+
+/// Create a new event loop, checking first that it can be created on the current thread.
+///
+/// On the platforms where it’s not allowed, winit panics when an event loop is created outside of
+/// the main thread.
+fn new_event_loop() -> Result<EventLoop<()>, GlutinError> {
+  if is_main_thread() {
+    Ok(EventLoop::new())
+  } else {
+    Err(GlutinError::NotMainThread)
+  }
+}
+
+/// Check whether the current thread is the main thread, on platforms requiring the event loop to be
+/// created on the main thread.
+#[cfg(target_os = "linux")]
+fn is_main_thread() -> bool {
+  unsafe { libc::syscall(libc::SYS_gettid) == libc::getpid().into() }
+}
+
+/// Check whether the current thread is the main thread, on platforms requiring the event loop to be
+/// created on the main thread.
+#[cfg(any(
+  target_os = "macos",
+  target_os = "ios",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "openbsd"
+))]
+fn is_main_thread() -> bool {
+  unsafe { libc::pthread_main_np() == 1 }
+}
+
+/// Check whether the current thread is the main thread, on platforms requiring the event loop to be
+/// created on the main thread.
+#[cfg(target_os = "netbsd")]
+fn is_main_thread() -> bool {
+  // the main thread is the first light-weight process of the process
+  unsafe { libc::_lwp_self() == 1 }
+}
+
+/// Check whether the current thread is the main thread, on platforms requiring the event loop to be
+/// created on the main thread.
+///
+/// Windows has no notion of main thread: like winit, the identifier of the thread running the C
+/// runtime initializers is recorded when the program starts, and compared against.
+#[cfg(target_os = "windows")]
+fn is_main_thread() -> bool {
+  use winapi::shared::minwindef::DWORD;
+  use winapi::um::processthreadsapi::GetCurrentThreadId;
+
+  static mut MAIN_THREAD_ID: DWORD = 0;
+
+  #[used]
+  #[link_section = ".CRT$XCU"]
+  static INIT_MAIN_THREAD_ID: unsafe extern "C" fn() = {
+    unsafe extern "C" fn init() {
+      MAIN_THREAD_ID = GetCurrentThreadId();
+    }
+
+    init
+  };
+
+  unsafe { GetCurrentThreadId() == MAIN_THREAD_ID }
+}
+
+/// Any thread can create the event loop on the other platforms.
+#[cfg(not(any(
+  target_os = "linux",
+  target_os = "macos",
+  target_os = "ios",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "openbsd",
+  target_os = "netbsd",
+  target_os = "windows"
+)))]
+fn is_main_thread() -> bool {
+  true
+}
+
+/// Get the theme of a window, on the platforms reporting it.
+#[cfg(target_os = "windows")]
+fn window_theme(window: &Window) -> Option<Theme> {
+  use glutin::platform::windows::WindowExtWindows;
+
+  Some(window.theme())
+}
+
+/// The other platforms don’t report the theme of windows.
+#[cfg(not(target_os = "windows"))]
+fn window_theme(_: &Window) -> Option<Theme> {
+  None
+}
+
+/// Get the GLSL version of the current OpenGL context, checking that the GL33 backend supports it.
+fn query_glsl_version(gl: &GL33) -> Result<(u16, u16), GlutinError> {
+  let version = unsafe { gl.state() }.borrow_mut().get_glsl_version();
+
+  match quirks::parse_glsl_version(&version) {
+    Some(glsl_version) if glsl_version >= (3, 30) => Ok(glsl_version),
+    _ => Err(GlutinError::UnsupportedGlslVersion { version }),
+  }
+}
+
+/// Detect the quirks of the driver of the current OpenGL context.
+fn detect_quirks(gl: &GL33) -> QuirkSet {
+  let mut state = unsafe { gl.state() }.borrow_mut();
+
+  QuirkSet::detect(
+    &state.get_vendor_name(),
+    &state.get_renderer_name(),
+    &state.get_gl_version(),
+  )
+}
+
+/// Query the extensions supported by the current OpenGL context.
+fn query_extensions() -> HashSet<String> {
+  let mut count = 0;
+  unsafe { gl::GetIntegerv(gl::NUM_EXTENSIONS, &mut count) };
+
+  (0..count.max(0) as gl::types::GLuint)
+    .filter_map(|i| unsafe {
+      let name = gl::GetStringi(gl::EXTENSIONS, i);
+
+      if name.is_null() {
+        None
+      } else {
+        Some(
+          CStr::from_ptr(name as *const c_char)
+            .to_string_lossy()
+            .into_owned(),
+        )
+      }
+    })
+    .collect()
+}
+
+/// Guard aborting the process when dropped, i.e. when a panic unwinds while it is alive.
+///
+/// Forget it once the code it protects has completed.
+struct AbortOnUnwind;
+
+impl Drop for AbortOnUnwind {
+  fn drop(&mut self) {
+    std::process::abort();
+  }
+}
+
+/// Guard unmapping (if mapped) and unbinding the buffer bound to `GL_COPY_WRITE_BUFFER` when
+/// dropped.
+///
+/// See [`GlutinSurface::with_mapped_buffer`].
+struct MappedBufferGuard {
+  mapped: bool,
+}
+
+impl Drop for MappedBufferGuard {
+  fn drop(&mut self) {
+    unsafe {
+      if self.mapped {
+        gl::UnmapBuffer(gl::COPY_WRITE_BUFFER);
+      }
+
+      gl::BindBuffer(gl::COPY_WRITE_BUFFER, 0);
+    }
+  }
+}
+
+/// Take the pending OpenGL errors.
+///
+/// [`GlutinError::OutOfMemory`] is returned if the GPU ran out of memory; otherwise, the other
+/// pending errors are returned, if any.
+fn take_gl_errors() -> Result<Vec<gl::types::GLenum>, GlutinError> {
+  let mut errors = Vec::new();
+
+  // bounded, as a lost context might keep on reporting errors
+  for _ in 0..MAX_PENDING_GL_ERRORS {
+    match unsafe { gl::GetError() } {
+      gl::NO_ERROR => break,
+      error => errors.push(error),
+    }
+  }
+
+  if errors.contains(&gl::OUT_OF_MEMORY) {
+    Err(GlutinError::OutOfMemory)
+  } else {
+    Ok(errors)
+  }
+}
+
+/// Flip the rows of an image, as OpenGL reads them from the bottom-left corner.
+fn flip_rows<T>(texels: &mut [T], row_len: usize) {
+  if row_len == 0 {
+    return;
+  }
+
+  let rows = texels.len() / row_len;
+
+  for y in 0..rows / 2 {
+    let (top, bottom) = texels.split_at_mut((rows - 1 - y) * row_len);
+    top[y * row_len..(y + 1) * row_len].swap_with_slice(&mut bottom[..row_len]);
   }
 }
@@ -0,0 +1,104 @@
+//! Presentation of textures to the back buffer.
+//!
+//! See [`GlutinSurface::frame`] and `GlutinSurface::present_texture`.
+//!
+//! [`GlutinSurface::frame`]: crate::GlutinSurface::frame
+
+use luminance::pipeline::TextureBinding;
+use luminance::pixel::{SamplerType, Type};
+use luminance::shader::{Program, Uniform, UniformBuilder, UniformInterface, UniformWarning};
+use luminance::tess::Tess;
+use luminance::texture::Dim2;
+use luminance_gl::GL33;
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+
+/// Vertex shader drawing a triangle covering the whole viewport, without any vertex data.
+pub(crate) const VS: &str = "
+out vec2 v_uv;
+
+void main() {
+  vec2 p = vec2(float((gl_VertexID & 1) << 2), float((gl_VertexID & 2) << 1)) - 1.;
+
+  v_uv = p * .5 + .5;
+  gl_Position = vec4(p, 0., 1.);
+}";
+
+/// Fragment shader sampling a texture with a sampler of type `S`.
+pub(crate) fn fragment_shader<S>() -> String
+where
+  S: SamplerType,
+{
+  let sampler = match S::sample_type() {
+    Type::Integral => "isampler2D",
+    Type::Unsigned => "usampler2D",
+    _ => "sampler2D",
+  };
+
+  format!(
+    "
+uniform {} tex;
+
+in vec2 v_uv;
+
+out vec4 frag;
+
+void main() {{
+  frag = vec4(texture(tex, v_uv));
+}}",
+    sampler
+  )
+}
+
+/// Uniform interface of the presentation program for textures sampled with `S`.
+pub(crate) struct PresentInterface<S> {
+  pub(crate) tex: Uniform<TextureBinding<Dim2, S>>,
+}
+
+impl<S> UniformInterface<GL33> for PresentInterface<S>
+where
+  S: SamplerType + 'static,
+{
+  fn uniform_interface<'a>(
+    builder: &mut UniformBuilder<'a, GL33>,
+    _: &mut (),
+  ) -> Result<Self, UniformWarning> {
+    Ok(PresentInterface {
+      tex: builder.ask("tex")?,
+    })
+  }
+}
+
+/// Presentation program for textures sampled with `S`.
+pub(crate) type PresentProgram<S> = Program<GL33, (), (), PresentInterface<S>>;
+
+/// Resources used to present textures, lazily created.
+#[derive(Default)]
+pub(crate) struct Presenter {
+  /// Presentation programs, per sampler type.
+  programs: HashMap<TypeId, Box<dyn Any>>,
+  /// Attribute-less triangle.
+  pub(crate) tess: Option<Tess<GL33, ()>>,
+}
+
+impl Presenter {
+  /// Take the presentation program for textures sampled with `S` out, if it was created already.
+  pub(crate) fn take_program<S>(&mut self) -> Option<PresentProgram<S>>
+  where
+    S: SamplerType + 'static,
+  {
+    self
+      .programs
+      .remove(&TypeId::of::<S>())
+      .and_then(|program| program.downcast().ok())
+      .map(|program| *program)
+  }
+
+  /// Put the presentation program for textures sampled with `S` back.
+  pub(crate) fn put_program<S>(&mut self, program: PresentProgram<S>)
+  where
+    S: SamplerType + 'static,
+  {
+    self.programs.insert(TypeId::of::<S>(), Box::new(program));
+  }
+}
@@ -0,0 +1,126 @@
+//! Driver quirks.
+//!
+//! Some drivers have known bugs that require applications to work around them. This module
+//! centralizes the detection of such drivers, based on the strings reported by OpenGL.
+
+/// Set of known driver bugs affecting the current OpenGL context.
+///
+/// A [`QuirkSet`] is computed when a [`GlutinSurface`] is created, from the vendor, renderer and
+/// version strings of the OpenGL context. You can get it with [`GlutinSurface::quirks`].
+///
+/// Detection is heuristic: it only flags drivers that are known to misbehave, so a driver not
+/// flagged here can still be buggy.
+///
+/// [`GlutinSurface`]: crate::GlutinSurface
+/// [`GlutinSurface::quirks`]: crate::GlutinSurface::quirks
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct QuirkSet {
+  /// The driver might present an incomplete frame if the command queue is not flushed before
+  /// swapping buffers.
+  ///
+  /// This is the case of the Windows drivers of older Intel integrated GPUs (up to Haswell). This
+  /// quirk is automatically worked around by [`GlutinSurface::swap_buffers`], which flushes before
+  /// swapping.
+  ///
+  /// [`GlutinSurface::swap_buffers`]: crate::GlutinSurface::swap_buffers
+  pub needs_explicit_flush_before_swap: bool,
+
+  /// The driver might produce a wrong result when resolving a multisample framebuffer by blitting
+  /// it.
+  ///
+  /// This is the case of Intel GPUs on Mesa versions older than 18. This quirk is not worked
+  /// around automatically, as it depends on how you render; you might want to avoid multisample
+  /// framebuffers altogether.
+  pub broken_msaa_resolve: bool,
+}
+
+impl QuirkSet {
+  /// Detect the quirks of a driver, based on the OpenGL vendor, renderer and version strings.
+  pub fn detect(vendor: &str, renderer: &str, version: &str) -> Self {
+    let is_intel = vendor.contains("Intel") || renderer.contains("Intel");
+    let mesa_version = mesa_version(version);
+
+    let needs_explicit_flush_before_swap =
+      is_intel && mesa_version.is_none() && is_pre_skylake_intel(renderer);
+    let broken_msaa_resolve = is_intel && mesa_version.map_or(false, |(major, _)| major < 18);
+
+    QuirkSet {
+      needs_explicit_flush_before_swap,
+      broken_msaa_resolve,
+    }
+  }
+}
+
+/// Extract the Mesa version out of an OpenGL version string.
+///
+/// Such strings look like `4.6 (Core Profile) Mesa 21.2.6`.
+fn mesa_version(version: &str) -> Option<(u32, u32)> {
+  let mut numbers = version
+    .split("Mesa ")
+    .nth(1)?
+    .split(|c: char| !c.is_ascii_digit())
+    .map(str::parse);
+
+  let major = numbers.next()?.ok()?;
+  let minor = numbers.next().and_then(Result::ok).unwrap_or(0);
+
+  Some((major, minor))
+}
+
+/// Parse a GLSL version string into its major and minor versions.
+///
+/// Such strings look like `4.60 NVIDIA` or `3.30`; the minor version is normalized to two digits,
+/// so that `3.3` reads as `(3, 30)`.
+pub(crate) fn parse_glsl_version(version: &str) -> Option<(u16, u16)> {
+  let mut numbers = version.split_whitespace().next()?.split('.');
+
+  let major = numbers.next()?.parse().ok()?;
+  let minor = numbers.next()?;
+  let minor = match minor.len() {
+    1 => minor.parse::<u16>().ok()? * 10,
+    _ => minor.get(..2)?.parse().ok()?,
+  };
+
+  Some((major, minor))
+}
+
+/// Check whether an Intel renderer string designates a GPU older than Skylake.
+///
+/// Those GPUs are named `Intel(R) HD Graphics` (Ironlake), or `Intel(R) HD Graphics NNNN` with a
+/// model number starting with 2 (Sandy Bridge), 3 or 4 (Ivy Bridge, Haswell).
+fn is_pre_skylake_intel(renderer: &str) -> bool {
+  match renderer.split("HD Graphics").nth(1) {
+    Some(model) => match model.split_whitespace().next() {
+      Some(model) => {
+        model.len() == 4
+          && model.chars().all(|c| c.is_ascii_digit())
+          && model.starts_with(|c| ('2'..='4').contains(&c))
+      }
+
+      None => true,
+    },
+
+    None => false,
+  }
+}
+
+/// Check whether a renderer string designates a software rasterizer.
+///
+/// Those are Mesa’s `llvmpipe`, `softpipe` and `swrast`, Google’s SwiftShader, the Microsoft
+/// Basic Render Driver and GDI Generic on Windows, and the Apple Software Renderer.
+pub(crate) fn is_software_renderer(renderer: &str) -> bool {
+  const SOFTWARE_RENDERERS: [&str; 7] = [
+    "llvmpipe",
+    "softpipe",
+    "swrast",
+    "SwiftShader",
+    "Microsoft Basic Render Driver",
+    "GDI Generic",
+    "Apple Software Renderer",
+  ];
+
+  SOFTWARE_RENDERERS
+    .iter()
+    .any(|software| renderer.contains(software))
+}
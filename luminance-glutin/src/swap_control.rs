@@ -0,0 +1,265 @@
+//! Runtime control of the swap interval.
+//!
+//! glutin only sets the swap interval when creating a context, so changing it afterwards goes
+//! through the swap control extensions of the platform (`WGL_EXT_swap_control`,
+//! `GLX_EXT_swap_control`, `GLX_MESA_swap_control`, `GLX_SGI_swap_control`, `eglSwapInterval`).
+//!
+//! Negative intervals ask for adaptive vsync, and require `WGL_EXT_swap_control_tear` or
+//! `GLX_EXT_swap_control_tear`.
+
+use glutin::{PossiblyCurrent, WindowedContext};
+
+/// Mechanism controlling the swap interval.
+///
+/// They differ in what they apply to and in the intervals they accept:
+///
+/// - [`SwapControl::Ext`] (`WGL_EXT_swap_control`, `GLX_EXT_swap_control`) applies to the
+///   drawable of the window, whichever context renders to it. `0` disables vertical
+///   synchronization, and negative intervals ask for adaptive vsync with the `_tear` variants.
+/// - [`SwapControl::Mesa`] (`GLX_MESA_swap_control`) applies to the drawable current with the
+///   context. `0` disables vertical synchronization; there is no adaptive vsync.
+/// - [`SwapControl::Sgi`] (`GLX_SGI_swap_control`) applies to the drawable current with the
+///   context, and only accepts strictly positive intervals: it cannot disable vertical
+///   synchronization, nor ask for adaptive vsync.
+/// - [`SwapControl::Egl`] (`eglSwapInterval`) applies to the surface current with the context.
+///   The interval is clamped to the range the configuration supports, which might exclude `0`;
+///   there is no adaptive vsync.
+///
+/// See [`GlutinSurface::force_swap_control`].
+///
+/// [`GlutinSurface::force_swap_control`]: crate::GlutinSurface::force_swap_control
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SwapControl {
+  /// `WGL_EXT_swap_control` on Windows, `GLX_EXT_swap_control` on X11.
+  Ext,
+  /// `GLX_MESA_swap_control`, on X11.
+  Mesa,
+  /// `GLX_SGI_swap_control`, on X11.
+  Sgi,
+  /// `eglSwapInterval`, with EGL (Wayland, and X11 with EGL).
+  Egl,
+}
+
+/// Set the swap interval of the current context, with the `forced` mechanism if any, or the best
+/// available one otherwise.
+///
+/// Returns the mechanism that changed the swap interval, or `None` if the platform doesn’t allow
+/// changing it (with the `forced` mechanism if any), or doesn’t support `interval`.
+///
+/// # Safety
+///
+/// `ctx` must be the current context.
+pub(crate) unsafe fn set_swap_interval(
+  ctx: &WindowedContext<PossiblyCurrent>,
+  interval: i32,
+  forced: Option<SwapControl>,
+) -> Option<SwapControl> {
+  platform::set_swap_interval(ctx, interval, forced)
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+  use super::SwapControl;
+  use glutin::{PossiblyCurrent, WindowedContext};
+  use std::ffi::CStr;
+  use std::mem;
+  use std::os::raw::c_char;
+
+  pub(super) unsafe fn set_swap_interval(
+    ctx: &WindowedContext<PossiblyCurrent>,
+    interval: i32,
+    forced: Option<SwapControl>,
+  ) -> Option<SwapControl> {
+    if forced.map_or(false, |forced| forced != SwapControl::Ext) {
+      return None;
+    }
+
+    if interval < 0 && !has_extension(ctx, "WGL_EXT_swap_control_tear") {
+      return None;
+    }
+
+    // wglGetProcAddress returns null for the extensions the driver doesn’t support
+    let swap_interval = ctx.get_proc_address("wglSwapIntervalEXT");
+
+    if swap_interval.is_null() {
+      return None;
+    }
+
+    let swap_interval: unsafe extern "system" fn(i32) -> i32 = mem::transmute(swap_interval);
+    (swap_interval(interval) != 0).then(|| SwapControl::Ext)
+  }
+
+  unsafe fn has_extension(ctx: &WindowedContext<PossiblyCurrent>, name: &str) -> bool {
+    let get_extensions = ctx.get_proc_address("wglGetExtensionsStringEXT");
+
+    if get_extensions.is_null() {
+      return false;
+    }
+
+    let get_extensions: unsafe extern "system" fn() -> *const c_char =
+      mem::transmute(get_extensions);
+    let extensions = get_extensions();
+
+    !extensions.is_null()
+      && CStr::from_ptr(extensions)
+        .to_string_lossy()
+        .split(' ')
+        .any(|ext| ext == name)
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::SwapControl;
+  use glutin::platform::unix::RawHandle;
+  use glutin::platform::ContextTraitExt;
+  use glutin::{PossiblyCurrent, WindowedContext};
+  use std::mem;
+  use std::os::raw::c_void;
+
+  pub(super) unsafe fn set_swap_interval(
+    ctx: &WindowedContext<PossiblyCurrent>,
+    interval: i32,
+    forced: Option<SwapControl>,
+  ) -> Option<SwapControl> {
+    match ctx.raw_handle() {
+      RawHandle::Egl(_) => set_egl_swap_interval(ctx, interval, forced),
+      #[cfg(feature = "x11")]
+      RawHandle::Glx(_) => set_glx_swap_interval(ctx, interval, forced),
+    }
+  }
+
+  unsafe fn set_egl_swap_interval(
+    ctx: &WindowedContext<PossiblyCurrent>,
+    interval: i32,
+    forced: Option<SwapControl>,
+  ) -> Option<SwapControl> {
+    if forced.map_or(false, |forced| forced != SwapControl::Egl) {
+      return None;
+    }
+
+    // EGL clamps the interval to EGL_MIN_SWAP_INTERVAL; there is no adaptive vsync
+    if interval < 0 {
+      return None;
+    }
+
+    let display = ctx.get_egl_display()?;
+
+    // eglSwapInterval is a core function, which eglGetProcAddress only returns since EGL 1.5 (or
+    // with EGL_KHR_get_all_proc_addresses); null otherwise
+    let swap_interval = ctx.get_proc_address("eglSwapInterval");
+
+    if swap_interval.is_null() {
+      return None;
+    }
+
+    let swap_interval: unsafe extern "C" fn(*const c_void, i32) -> u32 =
+      mem::transmute(swap_interval);
+    (swap_interval(display, interval) != 0).then(|| SwapControl::Egl)
+  }
+
+  #[cfg(feature = "x11")]
+  unsafe fn set_glx_swap_interval(
+    ctx: &WindowedContext<PossiblyCurrent>,
+    interval: i32,
+    forced: Option<SwapControl>,
+  ) -> Option<SwapControl> {
+    use glutin::platform::unix::WindowExtUnix;
+    use std::ffi::CStr;
+    use std::os::raw::{c_char, c_int, c_uint, c_ulong};
+
+    let window = ctx.window();
+    let display = window.xlib_display()?;
+    let drawable = window.xlib_window()?;
+    let screen = window.xlib_screen_id()?;
+
+    // glXGetProcAddress might return non-null pointers for unsupported functions, so the
+    // extensions are checked first
+    let query_extensions = ctx.get_proc_address("glXQueryExtensionsString");
+
+    if query_extensions.is_null() {
+      return None;
+    }
+
+    let query_extensions: unsafe extern "C" fn(*mut c_void, c_int) -> *const c_char =
+      mem::transmute(query_extensions);
+    let extensions = query_extensions(display, screen);
+
+    if extensions.is_null() {
+      return None;
+    }
+
+    let extensions = CStr::from_ptr(extensions).to_string_lossy();
+    let has_extension = |name| extensions.split(' ').any(|ext| ext == name);
+    let allowed = |control| forced.map_or(true, |forced| forced == control);
+
+    if interval < 0 && !has_extension("GLX_EXT_swap_control_tear") {
+      return None;
+    }
+
+    if allowed(SwapControl::Ext) && has_extension("GLX_EXT_swap_control") {
+      let swap_interval = ctx.get_proc_address("glXSwapIntervalEXT");
+
+      if !swap_interval.is_null() {
+        let swap_interval: unsafe extern "C" fn(*mut c_void, c_ulong, c_int) =
+          mem::transmute(swap_interval);
+        swap_interval(display, drawable, interval);
+        return Some(SwapControl::Ext);
+      }
+    }
+
+    if allowed(SwapControl::Mesa) && interval >= 0 && has_extension("GLX_MESA_swap_control") {
+      let swap_interval = ctx.get_proc_address("glXSwapIntervalMESA");
+
+      if !swap_interval.is_null() {
+        let swap_interval: unsafe extern "C" fn(c_uint) -> c_int = mem::transmute(swap_interval);
+
+        if swap_interval(interval as c_uint) == 0 {
+          return Some(SwapControl::Mesa);
+        }
+      }
+    }
+
+    if allowed(SwapControl::Sgi) && interval > 0 && has_extension("GLX_SGI_swap_control") {
+      let swap_interval = ctx.get_proc_address("glXSwapIntervalSGI");
+
+      if !swap_interval.is_null() {
+        let swap_interval: unsafe extern "C" fn(c_int) -> c_int = mem::transmute(swap_interval);
+
+        if swap_interval(interval) == 0 {
+          return Some(SwapControl::Sgi);
+        }
+      }
+    }
+
+    None
+  }
+}
+
+#[cfg(not(any(
+  target_os = "windows",
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+)))]
+mod platform {
+  use super::SwapControl;
+  use glutin::{PossiblyCurrent, WindowedContext};
+
+  pub(super) unsafe fn set_swap_interval(
+    _: &WindowedContext<PossiblyCurrent>,
+    _: i32,
+    _: Option<SwapControl>,
+  ) -> Option<SwapControl> {
+    None
+  }
+}
@@ -0,0 +1,114 @@
+//! Presentation timing feedback.
+//!
+//! glutin doesn’t report when frames are presented, so the time of the last vertical retrace is
+//! read with the sync control extension of the platform (`GLX_OML_sync_control`).
+
+use glutin::{PossiblyCurrent, WindowedContext};
+use std::time::Duration;
+
+/// Get the unadjusted system time (UST) of the last vertical retrace of the current context.
+///
+/// Returns `None` if the platform doesn’t report it.
+///
+/// # Safety
+///
+/// `ctx` must be the current context.
+pub(crate) unsafe fn last_retrace_time(ctx: &WindowedContext<PossiblyCurrent>) -> Option<Duration> {
+  platform::last_retrace_time(ctx)
+}
+
+#[cfg(all(
+  feature = "x11",
+  any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  )
+))]
+mod platform {
+  use glutin::platform::unix::{RawHandle, WindowExtUnix};
+  use glutin::platform::ContextTraitExt;
+  use glutin::{PossiblyCurrent, WindowedContext};
+  use std::ffi::CStr;
+  use std::mem;
+  use std::os::raw::{c_char, c_int, c_ulong, c_void};
+  use std::time::Duration;
+
+  pub(super) unsafe fn last_retrace_time(
+    ctx: &WindowedContext<PossiblyCurrent>,
+  ) -> Option<Duration> {
+    // EGL has no equivalent outside of Android and ChromeOS
+    if let RawHandle::Egl(_) = ctx.raw_handle() {
+      return None;
+    }
+
+    let window = ctx.window();
+    let display = window.xlib_display()?;
+    let drawable = window.xlib_window()?;
+    let screen = window.xlib_screen_id()?;
+
+    // glXGetProcAddress might return non-null pointers for unsupported functions, so the
+    // extensions are checked first
+    let query_extensions = ctx.get_proc_address("glXQueryExtensionsString");
+
+    if query_extensions.is_null() {
+      return None;
+    }
+
+    let query_extensions: unsafe extern "C" fn(*mut c_void, c_int) -> *const c_char =
+      mem::transmute(query_extensions);
+    let extensions = query_extensions(display, screen);
+
+    if extensions.is_null()
+      || !CStr::from_ptr(extensions)
+        .to_string_lossy()
+        .split(' ')
+        .any(|ext| ext == "GLX_OML_sync_control")
+    {
+      return None;
+    }
+
+    let get_sync_values = ctx.get_proc_address("glXGetSyncValuesOML");
+
+    if get_sync_values.is_null() {
+      return None;
+    }
+
+    let get_sync_values: unsafe extern "C" fn(
+      *mut c_void,
+      c_ulong,
+      *mut i64,
+      *mut i64,
+      *mut i64,
+    ) -> c_int = mem::transmute(get_sync_values);
+    let (mut ust, mut msc, mut sbc) = (0, 0, 0);
+
+    if get_sync_values(display, drawable, &mut ust, &mut msc, &mut sbc) == 0 || ust <= 0 {
+      return None;
+    }
+
+    // the unit of UST is left to the implementation; Mesa uses microseconds of CLOCK_MONOTONIC
+    Some(Duration::from_micros(ust as u64))
+  }
+}
+
+#[cfg(not(all(
+  feature = "x11",
+  any(
+    target_os = "linux",
+    target_os = "dragonfly",
+    target_os = "freebsd",
+    target_os = "netbsd",
+    target_os = "openbsd"
+  )
+)))]
+mod platform {
+  use glutin::{PossiblyCurrent, WindowedContext};
+  use std::time::Duration;
+
+  pub(super) unsafe fn last_retrace_time(_: &WindowedContext<PossiblyCurrent>) -> Option<Duration> {
+    None
+  }
+}
@@ -0,0 +1,69 @@
+//! Progress of the window in the taskbar.
+
+use glutin::window::Window;
+
+/// Show `progress` (from `0` to `1`) in the taskbar entry of `window`, or hide it if `None`.
+pub(crate) fn set_progress(window: &Window, progress: Option<f32>) {
+  platform::set_progress(window, progress)
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+  use glutin::platform::windows::WindowExtWindows;
+  use glutin::window::Window;
+  use std::ptr;
+  use winapi::shared::windef::HWND;
+  use winapi::shared::winerror::SUCCEEDED;
+  use winapi::shared::wtypesbase::CLSCTX_INPROC_SERVER;
+  use winapi::um::combaseapi::CoCreateInstance;
+  use winapi::um::shobjidl_core::{CLSID_TaskbarList, ITaskbarList3, TBPF_NOPROGRESS, TBPF_NORMAL};
+  use winapi::Interface;
+
+  /// Resolution of the progress reported to the taskbar.
+  const PROGRESS_TOTAL: u64 = 10_000;
+
+  pub(super) fn set_progress(window: &Window, progress: Option<f32>) {
+    let hwnd = window.hwnd() as HWND;
+
+    unsafe {
+      let mut taskbar: *mut ITaskbarList3 = ptr::null_mut();
+      let hr = CoCreateInstance(
+        &CLSID_TaskbarList,
+        ptr::null_mut(),
+        CLSCTX_INPROC_SERVER,
+        &ITaskbarList3::uuidof(),
+        &mut taskbar as *mut _ as *mut _,
+      );
+
+      // COM is not initialized on this thread, or the shell has no taskbar
+      if !SUCCEEDED(hr) || taskbar.is_null() {
+        return;
+      }
+
+      let taskbar = &*taskbar;
+
+      if SUCCEEDED(taskbar.HrInit()) {
+        match progress {
+          Some(progress) => {
+            let completed = (progress.clamp(0., 1.) * PROGRESS_TOTAL as f32).round() as u64;
+            taskbar.SetProgressState(hwnd, TBPF_NORMAL);
+            taskbar.SetProgressValue(hwnd, completed, PROGRESS_TOTAL);
+          }
+
+          None => {
+            taskbar.SetProgressState(hwnd, TBPF_NOPROGRESS);
+          }
+        }
+      }
+
+      taskbar.Release();
+    }
+  }
+}
+
+#[cfg(not(target_os = "windows"))]
+mod platform {
+  use glutin::window::Window;
+
+  pub(super) fn set_progress(_: &Window, _: Option<f32>) {}
+}
@@ -0,0 +1,189 @@
+//! Frame timing.
+
+use std::time::{Duration, Instant};
+
+/// Statistics about the time between buffer swaps.
+///
+/// See [`GlutinSurface::enable_swap_timing`].
+///
+/// [`GlutinSurface::enable_swap_timing`]: crate::GlutinSurface::enable_swap_timing
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq)]
+pub struct SwapTimingStats {
+  /// Number of measured gaps between two swaps.
+  pub count: u64,
+  /// Mean gap between two swaps.
+  pub mean: Duration,
+  /// Standard deviation of the gaps between two swaps, i.e. the jitter.
+  pub stddev: Duration,
+  /// Longest gap between two swaps.
+  pub max: Duration,
+}
+
+/// Swap timing accumulator.
+#[derive(Debug, Default)]
+pub(crate) struct SwapTiming {
+  last_swap: Option<Instant>,
+  count: u64,
+  // running mean and sum of squared deviations (Welford), in seconds
+  mean: f64,
+  m2: f64,
+  max: Duration,
+}
+
+impl SwapTiming {
+  /// Record a swap happening now.
+  pub(crate) fn record(&mut self) {
+    let now = Instant::now();
+
+    if let Some(last_swap) = self.last_swap.replace(now) {
+      let gap = now - last_swap;
+      let secs = gap.as_secs_f64();
+
+      self.count += 1;
+      let delta = secs - self.mean;
+      self.mean += delta / self.count as f64;
+      self.m2 += delta * (secs - self.mean);
+      self.max = self.max.max(gap);
+    }
+  }
+
+  /// Compute the statistics of the recorded swaps.
+  pub(crate) fn stats(&self) -> SwapTimingStats {
+    let variance = if self.count > 1 {
+      self.m2 / (self.count - 1) as f64
+    } else {
+      0.
+    };
+
+    SwapTimingStats {
+      count: self.count,
+      mean: Duration::from_secs_f64(self.mean),
+      stddev: Duration::from_secs_f64(variance.sqrt()),
+      max: self.max,
+    }
+  }
+}
+
+/// Clock sampled when buffers are swapped.
+///
+/// Times are relative to the creation of the clock. They come from the presentation timing
+/// feedback of the platform when available, and from the monotonic clock otherwise.
+#[derive(Debug)]
+pub(crate) struct PresentationClock {
+  origin: Instant,
+  // platform time of the origin; None until the first feedback
+  feedback_origin: Option<Duration>,
+  // whether the platform still gives feedback
+  feedback: bool,
+  last: Option<Duration>,
+}
+
+impl PresentationClock {
+  /// Create a clock starting now.
+  pub(crate) fn new() -> Self {
+    PresentationClock {
+      origin: Instant::now(),
+      feedback_origin: None,
+      feedback: true,
+      last: None,
+    }
+  }
+
+  /// Whether the platform feedback should be sampled on the next swap.
+  pub(crate) fn wants_feedback(&self) -> bool {
+    self.feedback
+  }
+
+  /// Record a swap happening now, with the platform feedback, if any.
+  pub(crate) fn record(&mut self, feedback: Option<Duration>) {
+    let elapsed = self.origin.elapsed();
+    let feedback_origin = match (self.feedback_origin, feedback) {
+      (Some(origin), Some(_)) => Some(origin),
+      // align the platform clock on the monotonic one on the first feedback
+      (None, Some(feedback)) => feedback.checked_sub(elapsed),
+      (_, None) => None,
+    };
+
+    let time = match (feedback, feedback_origin) {
+      (Some(feedback), Some(origin)) => feedback.saturating_sub(origin),
+      _ => {
+        // stop asking a platform that doesn’t answer
+        self.feedback = false;
+        elapsed
+      }
+    };
+
+    self.feedback_origin = feedback_origin;
+    // never go back in time, even if the platform and monotonic clocks drift apart
+    self.last = Some(self.last.map_or(time, |last| last.max(time)));
+  }
+
+  /// Forget about the platform feedback, e.g. when the context is recreated.
+  pub(crate) fn reset_feedback(&mut self) {
+    self.feedback_origin = None;
+    self.feedback = true;
+  }
+
+  /// Time of the last recorded swap.
+  pub(crate) fn last(&self) -> Option<Duration> {
+    self.last
+  }
+}
+
+/// Frame rate cap, waiting before swaps to keep them apart.
+#[derive(Debug, Default)]
+pub(crate) struct FrameLimiter {
+  last_frame: Option<Instant>,
+}
+
+impl FrameLimiter {
+  /// Wait until a frame can be presented without exceeding `fps` frames per second, if any, and
+  /// record it.
+  pub(crate) fn wait(&mut self, fps: Option<u32>) {
+    if let (Some(fps), Some(last_frame)) = (fps, self.last_frame) {
+      let next_frame = last_frame + Duration::from_secs(1) / fps.max(1);
+      let now = Instant::now();
+
+      if next_frame > now {
+        std::thread::sleep(next_frame - now);
+      }
+    }
+
+    self.last_frame = Some(Instant::now());
+  }
+}
+
+/// Frame budget, reporting the frames taking longer than it.
+pub(crate) struct FrameBudget {
+  budget: Duration,
+  on_overrun: Box<dyn FnMut(Duration)>,
+  frame_start: Option<Instant>,
+}
+
+impl FrameBudget {
+  /// Create a frame budget calling `on_overrun` with the duration of the frames exceeding it.
+  pub(crate) fn new(budget: Duration, on_overrun: Box<dyn FnMut(Duration)>) -> Self {
+    FrameBudget {
+      budget,
+      on_overrun,
+      frame_start: None,
+    }
+  }
+
+  /// End the current frame, reporting it if it exceeded the budget.
+  pub(crate) fn end_frame(&mut self) {
+    if let Some(frame_start) = self.frame_start.take() {
+      let duration = frame_start.elapsed();
+
+      if duration > self.budget {
+        (self.on_overrun)(duration);
+      }
+    }
+  }
+
+  /// Start a new frame now.
+  pub(crate) fn start_frame(&mut self) {
+    self.frame_start = Some(Instant::now());
+  }
+}
@@ -0,0 +1,82 @@
+//! Split-screen viewports.
+
+/// Layout of split-screen viewports.
+///
+/// See [`GlutinSurface::viewports`].
+///
+/// [`GlutinSurface::viewports`]: crate::GlutinSurface::viewports
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum SplitLayout {
+  /// A single viewport covering the whole surface.
+  Full,
+  /// Two viewports side by side: left, then right.
+  LeftRight,
+  /// Two viewports on top of each other: top, then bottom.
+  TopBottom,
+  /// Four viewports in a 2×2 grid: top-left, top-right, bottom-left, then bottom-right.
+  Quad,
+}
+
+/// Compute the viewports of `layout` in a surface of the given size.
+///
+/// Viewports are expressed as `[x, y, width, height]`, with the origin in the lower-left corner,
+/// and tile the surface exactly: when a size cannot be split evenly, the last column (or the top
+/// row) gets the extra pixel.
+pub(crate) fn split(size: [u32; 2], layout: SplitLayout) -> Vec<[u32; 4]> {
+  let [width, height] = size;
+  let (left, bottom) = (width / 2, height / 2);
+  let (right, top) = (width - left, height - bottom);
+
+  match layout {
+    SplitLayout::Full => vec![[0, 0, width, height]],
+    SplitLayout::LeftRight => vec![[0, 0, left, height], [left, 0, right, height]],
+    SplitLayout::TopBottom => vec![[0, bottom, width, top], [0, 0, width, bottom]],
+    SplitLayout::Quad => vec![
+      [0, bottom, left, top],
+      [left, bottom, right, top],
+      [0, 0, left, bottom],
+      [left, 0, right, bottom],
+    ],
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn quad() {
+    assert_eq!(
+      split([800, 600], SplitLayout::Quad),
+      vec![
+        [0, 300, 400, 300],
+        [400, 300, 400, 300],
+        [0, 0, 400, 300],
+        [400, 0, 400, 300]
+      ]
+    );
+  }
+
+  #[test]
+  fn quad_odd_size() {
+    let viewports = split([801, 601], SplitLayout::Quad);
+    let area: u32 = viewports.iter().map(|[_, _, w, h]| w * h).sum();
+
+    assert_eq!(viewports[0], [0, 300, 400, 301]);
+    assert_eq!(viewports[3], [400, 0, 401, 300]);
+    assert_eq!(area, 801 * 601);
+  }
+
+  #[test]
+  fn halves() {
+    assert_eq!(
+      split([800, 600], SplitLayout::LeftRight),
+      vec![[0, 0, 400, 600], [400, 0, 400, 600]]
+    );
+    assert_eq!(
+      split([800, 600], SplitLayout::TopBottom),
+      vec![[0, 300, 800, 300], [0, 0, 800, 300]]
+    );
+  }
+}
@@ -0,0 +1,100 @@
+//! Windowing backends.
+
+use glutin::{PossiblyCurrent, WindowedContext};
+
+/// Platform API providing the OpenGL context of a surface.
+///
+/// Some extensions only exist with a given backend: for instance, the swap interval is controlled
+/// with `GLX_*_swap_control` on GLX, `WGL_EXT_swap_control` on WGL and `eglSwapInterval` on EGL
+/// (see [`SwapControl`]), and presentation timing feedback is only available on GLX.
+///
+/// See [`GlutinSurface::windowing_backend`].
+///
+/// [`SwapControl`]: crate::SwapControl
+/// [`GlutinSurface::windowing_backend`]: crate::GlutinSurface::windowing_backend
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum WindowingBackend {
+  /// EGL, on Wayland, Android, and on X11 and Windows when glutin falls back to it.
+  Egl,
+  /// GLX, on X11.
+  Glx,
+  /// WGL, on Windows.
+  Wgl,
+  /// CGL (through `NSOpenGLContext`), on macOS.
+  Cgl,
+  /// EAGL, on iOS.
+  Eagl,
+}
+
+/// Get the backend providing `ctx`.
+pub(crate) fn windowing_backend(ctx: &WindowedContext<PossiblyCurrent>) -> WindowingBackend {
+  platform::windowing_backend(ctx)
+}
+
+#[cfg(target_os = "windows")]
+mod platform {
+  use super::WindowingBackend;
+  use glutin::platform::windows::RawHandle;
+  use glutin::platform::ContextTraitExt;
+  use glutin::{PossiblyCurrent, WindowedContext};
+
+  pub(super) fn windowing_backend(ctx: &WindowedContext<PossiblyCurrent>) -> WindowingBackend {
+    match unsafe { ctx.raw_handle() } {
+      RawHandle::Wgl(_) => WindowingBackend::Wgl,
+      RawHandle::Egl(_) => WindowingBackend::Egl,
+    }
+  }
+}
+
+#[cfg(any(
+  target_os = "linux",
+  target_os = "dragonfly",
+  target_os = "freebsd",
+  target_os = "netbsd",
+  target_os = "openbsd"
+))]
+mod platform {
+  use super::WindowingBackend;
+  use glutin::platform::unix::RawHandle;
+  use glutin::platform::ContextTraitExt;
+  use glutin::{PossiblyCurrent, WindowedContext};
+
+  pub(super) fn windowing_backend(ctx: &WindowedContext<PossiblyCurrent>) -> WindowingBackend {
+    match unsafe { ctx.raw_handle() } {
+      RawHandle::Egl(_) => WindowingBackend::Egl,
+      #[cfg(feature = "x11")]
+      RawHandle::Glx(_) => WindowingBackend::Glx,
+    }
+  }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+  use super::WindowingBackend;
+  use glutin::{PossiblyCurrent, WindowedContext};
+
+  pub(super) fn windowing_backend(_: &WindowedContext<PossiblyCurrent>) -> WindowingBackend {
+    WindowingBackend::Cgl
+  }
+}
+
+#[cfg(target_os = "ios")]
+mod platform {
+  use super::WindowingBackend;
+  use glutin::{PossiblyCurrent, WindowedContext};
+
+  pub(super) fn windowing_backend(_: &WindowedContext<PossiblyCurrent>) -> WindowingBackend {
+    WindowingBackend::Eagl
+  }
+}
+
+#[cfg(target_os = "android")]
+mod platform {
+  use super::WindowingBackend;
+  use glutin::{PossiblyCurrent, WindowedContext};
+
+  pub(super) fn windowing_backend(_: &WindowedContext<PossiblyCurrent>) -> WindowingBackend {
+    WindowingBackend::Egl
+  }
+}
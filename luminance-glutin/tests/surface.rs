@@ -0,0 +1,308 @@
+//! Tests requiring a window and an OpenGL context.
+//!
+//! luminance only hands out one graphics state per thread, and most platforms require the event
+//! loop to be created on the main thread, so the test harness is disabled for this file: `main`
+//! creates a single hidden surface on the main thread, and the tests run one after the other with
+//! it. They are skipped when no display is available.
+
+use glutin::dpi::PhysicalSize;
+use glutin::event_loop::EventLoop;
+use glutin::window::WindowBuilder;
+use luminance::context::GraphicsContext;
+use luminance::pipeline::{PipelineError, PipelineState};
+use luminance::pixel::NormRGBA8UI;
+use luminance::scissor::ScissorRegion;
+use luminance::texture::{Dim2, Sampler, TexelUpload};
+use luminance_glutin::GlutinSurface;
+use std::os::raw::c_void;
+use std::thread;
+use std::time::{Duration, Instant};
+
+type Test = fn(&mut GlutinSurface, &EventLoop<()>);
+
+const TESTS: &[(&str, Test)] = &[
+  ("texture_after_raw_gl", texture_after_raw_gl as Test),
+  ("scissor_stack", scissor_stack as Test),
+  ("shared_context", shared_context as Test),
+  ("framebuffer_caching", framebuffer_caching as Test),
+];
+
+fn main() {
+  if !has_display() {
+    println!("no display available, skipping {} tests", TESTS.len());
+    return;
+  }
+
+  let window_builder = WindowBuilder::new()
+    .with_visible(false)
+    .with_inner_size(PhysicalSize::new(64, 64));
+  let (mut surface, event_loop) =
+    GlutinSurface::new_gl33(window_builder, 0).expect("GlutinSurface creation");
+
+  for (name, test) in TESTS {
+    print!("test {} ... ", name);
+    test(&mut surface, &event_loop);
+    println!("ok");
+  }
+}
+
+#[cfg(all(
+  unix,
+  not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+))]
+fn has_display() -> bool {
+  std::env::var_os("DISPLAY").is_some() || std::env::var_os("WAYLAND_DISPLAY").is_some()
+}
+
+#[cfg(not(all(
+  unix,
+  not(any(target_os = "macos", target_os = "ios", target_os = "android"))
+)))]
+fn has_display() -> bool {
+  true
+}
+
+/// Create, upload to and resize a texture after raw OpenGL code, which leaves the texture unit
+/// unknown to luminance.
+fn texture_after_raw_gl(surface: &mut GlutinSurface, _: &EventLoop<()>) {
+  surface
+    .with_raw_gl(|| unsafe { gl::ActiveTexture(gl::TEXTURE3) })
+    .unwrap();
+
+  let red = [[255, 0, 0, 255]; 4];
+  let mut texture = surface
+    .new_texture::<Dim2, NormRGBA8UI>(
+      [2, 2],
+      Sampler::default(),
+      TexelUpload::base_level(&red[..], 0),
+    )
+    .unwrap();
+
+  surface.with_raw_gl(|| ()).unwrap();
+  let green = [[0, 255, 0, 255]; 4];
+  texture
+    .upload(TexelUpload::base_level(&green[..], 0))
+    .unwrap();
+
+  surface.with_raw_gl(|| ()).unwrap();
+  let blue = [[0, 0, 255, 255]; 16];
+  texture
+    .resize([4, 4], TexelUpload::base_level(&blue[..], 0))
+    .unwrap();
+
+  let texels = texture.get_raw_texels().unwrap();
+  assert_eq!(texels, blue.concat());
+}
+
+/// Push and pop nested scissor regions, clearing a framebuffer within the current one.
+fn scissor_stack(surface: &mut GlutinSurface, _: &EventLoop<()>) {
+  let a = ScissorRegion {
+    x: 0,
+    y: 0,
+    width: 48,
+    height: 48,
+  };
+  let b = ScissorRegion {
+    x: 16,
+    y: 16,
+    width: 48,
+    height: 48,
+  };
+  let a_and_b = ScissorRegion {
+    x: 16,
+    y: 16,
+    width: 32,
+    height: 32,
+  };
+
+  assert_eq!(surface.push_scissor(a), a);
+  assert_eq!(surface.push_scissor(b), a_and_b);
+  assert_eq!(surface.scissor(), Some(a_and_b));
+  assert_eq!(scissor_gl_state(surface), Some([16, 16, 32, 32]));
+
+  let mut framebuffer = surface
+    .new_framebuffer::<Dim2, NormRGBA8UI, ()>([64, 64], 0, Sampler::default())
+    .unwrap();
+
+  // clear everything in black, then the current scissor region in white
+  let black = PipelineState::default().set_clear_color([0., 0., 0., 1.]);
+  let white = PipelineState::default()
+    .set_clear_color([1., 1., 1., 1.])
+    .set_scissor(surface.scissor());
+
+  for state in [black, white] {
+    surface
+      .new_pipeline_gate()
+      .pipeline(&framebuffer, &state, |_, _| Ok::<_, PipelineError>(()))
+      .into_result()
+      .unwrap();
+  }
+
+  let texels = framebuffer.color_slot().get_raw_texels().unwrap();
+  let pixel = |x: usize, y: usize| &texels[(y * 64 + x) * 4..][..4];
+
+  // inside the intersection
+  assert_eq!(pixel(16, 16), [255; 4]);
+  assert_eq!(pixel(47, 47), [255; 4]);
+  // in a only
+  assert_eq!(pixel(8, 8), [0, 0, 0, 255]);
+  assert_eq!(pixel(32, 8), [0, 0, 0, 255]);
+  // in b only
+  assert_eq!(pixel(56, 56), [0, 0, 0, 255]);
+  assert_eq!(pixel(56, 32), [0, 0, 0, 255]);
+  // in neither
+  assert_eq!(pixel(56, 8), [0, 0, 0, 255]);
+
+  assert_eq!(surface.pop_scissor(), Some(a_and_b));
+  assert_eq!(surface.scissor(), Some(a));
+  assert_eq!(scissor_gl_state(surface), Some([0, 0, 48, 48]));
+
+  assert_eq!(surface.pop_scissor(), Some(a));
+  assert_eq!(surface.scissor(), None);
+  assert_eq!(scissor_gl_state(surface), None);
+
+  assert_eq!(surface.pop_scissor(), None);
+}
+
+/// Get the OpenGL scissor box, if the scissor test is enabled.
+fn scissor_gl_state(surface: &mut GlutinSurface) -> Option<[i32; 4]> {
+  surface
+    .with_raw_gl(|| unsafe {
+      if gl::IsEnabled(gl::SCISSOR_TEST) == gl::FALSE {
+        return None;
+      }
+
+      let mut scissor_box = [0; 4];
+      gl::GetIntegerv(gl::SCISSOR_BOX, scissor_box.as_mut_ptr());
+      Some(scissor_box)
+    })
+    .unwrap()
+}
+
+/// Upload a texture with a worker context, and read it back with the surface it shares its objects
+/// with.
+///
+/// luminance textures cannot cross threads, so the texture is handed over as an OpenGL name.
+fn shared_context(surface: &mut GlutinSurface, event_loop: &EventLoop<()>) {
+  let texels: [[u8; 4]; 4] = [
+    [255, 0, 0, 255],
+    [0, 255, 0, 255],
+    [0, 0, 255, 255],
+    [1, 2, 3, 4],
+  ];
+  let worker = surface.create_worker_context(event_loop).unwrap();
+
+  let texture = thread::spawn(move || {
+    let mut worker = worker.make_current().unwrap();
+    let mut texture = 0;
+
+    unsafe {
+      gl::GenTextures(1, &mut texture);
+      gl::BindTexture(gl::TEXTURE_2D, texture);
+      gl::TexImage2D(
+        gl::TEXTURE_2D,
+        0,
+        gl::RGBA8 as _,
+        2,
+        2,
+        0,
+        gl::RGBA,
+        gl::UNSIGNED_BYTE,
+        texels.as_ptr() as *const c_void,
+      );
+      gl::BindTexture(gl::TEXTURE_2D, 0);
+    }
+
+    // make the upload visible to the surface before handing the texture over
+    worker.finish();
+    texture
+  })
+  .join()
+  .unwrap();
+
+  let read = surface
+    .with_raw_gl(|| unsafe {
+      let mut read = [[0u8; 4]; 4];
+
+      gl::BindTexture(gl::TEXTURE_2D, texture);
+      gl::PixelStorei(gl::PACK_ALIGNMENT, 1);
+      gl::GetTexImage(
+        gl::TEXTURE_2D,
+        0,
+        gl::RGBA,
+        gl::UNSIGNED_BYTE,
+        read.as_mut_ptr() as *mut c_void,
+      );
+      gl::BindTexture(gl::TEXTURE_2D, 0);
+      gl::DeleteTextures(1, &texture);
+
+      read
+    })
+    .unwrap();
+
+  assert_eq!(read, texels);
+}
+
+/// Reuse the cached back buffer and scratch framebuffer until the surface is resized.
+fn framebuffer_caching(surface: &mut GlutinSurface, _: &EventLoop<()>) {
+  let size = surface.size();
+  let mut cache = None;
+
+  for _ in 0..100 {
+    let back_buffer = surface.back_buffer_into(&mut cache).unwrap();
+    assert_eq!(back_buffer.size(), size);
+  }
+
+  // clear the scratch framebuffer once; recreating it would lose its content
+  let marker = vec![[32u8, 64, 128, 255]; (size[0] * size[1]) as usize];
+  surface
+    .render_to_texture::<NormRGBA8UI, (), _>(|surface, framebuffer| {
+      let state =
+        PipelineState::default().set_clear_color([32. / 255., 64. / 255., 128. / 255., 1.]);
+      surface
+        .new_pipeline_gate()
+        .pipeline(framebuffer, &state, |_, _| Ok::<_, PipelineError>(()))
+        .into_result()
+        .unwrap();
+    })
+    .unwrap();
+
+  for _ in 0..100 {
+    let scratch = surface.scratch_framebuffer::<NormRGBA8UI, ()>().unwrap();
+    assert_eq!(scratch.size(), size);
+  }
+
+  let scratch = surface.scratch_framebuffer::<NormRGBA8UI, ()>().unwrap();
+  let texels = scratch.color_slot().get_raw_texels().unwrap();
+  assert_eq!(texels, marker.concat());
+
+  // both are recreated with the new size after a resize
+  let new_size = [32, 48];
+  resize_window(surface, new_size);
+
+  let back_buffer = surface.back_buffer_into(&mut cache).unwrap();
+  assert_eq!(back_buffer.size(), new_size);
+
+  let scratch = surface.scratch_framebuffer::<NormRGBA8UI, ()>().unwrap();
+  assert_eq!(scratch.size(), new_size);
+
+  resize_window(surface, size);
+  surface.clear_scratch_framebuffers();
+}
+
+/// Resize the window and the surface, waiting for the window to report its new size.
+fn resize_window(surface: &mut GlutinSurface, size: [u32; 2]) {
+  let deadline = Instant::now() + Duration::from_secs(1);
+
+  surface
+    .ctx
+    .window()
+    .set_inner_size(PhysicalSize::new(size[0], size[1]));
+
+  while surface.size() != size {
+    assert!(Instant::now() < deadline, "the window wasn’t resized");
+    thread::sleep(Duration::from_millis(10));
+  }
+
+  surface.resize(size);
+}
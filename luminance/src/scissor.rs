@@ -18,3 +18,28 @@ pub struct ScissorRegion {
   /// The screen height of the scissor region.
   pub height: u32,
 }
+
+impl ScissorRegion {
+  /// Intersect two scissor regions.
+  ///
+  /// If the regions don’t overlap, the returned region is empty (its width or height is `0`).
+  pub fn intersection(&self, other: &ScissorRegion) -> ScissorRegion {
+    let x = self.x.max(other.x);
+    let y = self.y.max(other.y);
+    let right = self
+      .x
+      .saturating_add(self.width)
+      .min(other.x.saturating_add(other.width));
+    let top = self
+      .y
+      .saturating_add(self.height)
+      .min(other.y.saturating_add(other.height));
+
+    ScissorRegion {
+      x,
+      y,
+      width: right.saturating_sub(x),
+      height: top.saturating_sub(y),
+    }
+  }
+}